@@ -0,0 +1,89 @@
+//! Day 1: Calorie Counting. Finds the elves carrying the most calories, in
+//! one pass over the input.
+
+use std::io::Write;
+
+use common::*;
+
+pub const DAY: Day = Day::new::<Puzzle>(2022, 1, "elf-calories", env!("CARGO_MANIFEST_DIR"));
+
+common::example_tests!(DAY);
+
+/// The `count` largest totals of calories carried by an elf, from most to
+/// least, read in one pass so that only `count` totals are ever held.
+pub fn top_totals(reader: impl std::io::BufRead, count: usize) -> Result<Vec<u32>> {
+    let mut top = Vec::with_capacity(count + 1);
+    let mut keep = |total| {
+        let at = top.partition_point(|&kept| kept >= total);
+        top.insert(at, total);
+        top.truncate(count);
+    };
+
+    let mut total = None;
+    for line in stream::Lines::new(reader) {
+        let line = line?;
+        if line.is_empty() {
+            total.take().map(&mut keep);
+            continue;
+        }
+
+        let calories = line.parse_all(Scanner::int::<u32>)?;
+        let sum = total.unwrap_or(0u32).checked_add(calories);
+        total = Some(sum.ok_or_else(|| line.error("Too many calories to count"))?);
+    }
+    total.map(keep);
+
+    Ok(top)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    /// The three largest totals of calories carried by an elf, from most to
+    /// least
+    type Parsed = Vec<u32>;
+
+    fn parse(data: &str) -> Result<Self::Parsed> {
+        top_totals(data.as_bytes(), 3)
+    }
+
+    fn part_one(top_total_calories: &Self::Parsed) -> Result<Answer> {
+        let most_calories = top_total_calories
+            .first()
+            .ok_or_else(|| e!("There weren't any elves"))?;
+
+        Ok((*most_calories).into())
+    }
+
+    fn part_two(top_total_calories: &Self::Parsed) -> Result<Answer> {
+        let top_3_calories_total = top_total_calories.iter().sum::<u32>();
+
+        Ok(top_3_calories_total.into())
+    }
+
+    /// Elves carrying a handful of snacks each.
+    fn generate(rng: &mut Rng, out: &mut Output) -> std::io::Result<()> {
+        loop {
+            for _ in 0..rng.between(1, 12) {
+                writeln!(out, "{}", rng.between(1000, 60000))?;
+            }
+            if out.full() {
+                return Ok(());
+            }
+            writeln!(out)?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::top_totals;
+
+    #[test]
+    fn errors_have_line_numbers() {
+        let failure = top_totals("1\n2\n\n3\nfour\n".as_bytes(), 3).unwrap_err();
+        assert_eq!(failure.location().map(|location| location.line), Some(5));
+
+        assert_eq!(top_totals("1\n\n\n5\n\n2\n".as_bytes(), 2).unwrap(), [5, 2]);
+    }
+}
@@ -0,0 +1,415 @@
+//! Day 10: Cathode-Ray Tube. A [`CentralProcessingUnit`] runs a program,
+//! measuring signal strength and drawing on a [`CathodeRayTube`] as it goes.
+
+use std::{fmt::Display, io::Write};
+
+use common::{
+    checkpoint::{self, Progress, Saved, Schedule, State},
+    image::{Colour, Image},
+    *,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(2022, 10, "cathode-ray_tube", env!("CARGO_MANIFEST_DIR"));
+
+common::example_tests!(DAY);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    AddX(i32),
+    NoOp,
+}
+
+impl Instruction {
+    /// How many cycles it takes
+    fn cycles(&self) -> usize {
+        match self {
+            Instruction::AddX(_) => 2,
+            Instruction::NoOp => 1,
+        }
+    }
+
+    /// Reads `addx <value>` or `noop`.
+    pub fn scan(s: &mut Scanner) -> Result<Self> {
+        let instruction = match s.word()? {
+            "addx" => {
+                s.literal(" ")?;
+                Instruction::AddX(s.int()?)
+            }
+            "noop" => Instruction::NoOp,
+            unknown => return Err(s.error_at(unknown, format!("Unrecognised command: {unknown}"))),
+        };
+
+        Ok(instruction)
+    }
+}
+
+pub const WIDTH: usize = 40;
+pub const HEIGHT: usize = 6;
+
+/// The screen, which is lit wherever the sprite was when its pixel was
+/// drawn
+#[derive(Debug, PartialEq, Eq)]
+pub struct CathodeRayTube(Grid<bool>);
+
+impl Default for CathodeRayTube {
+    fn default() -> Self {
+        Self(Grid::new(WIDTH, HEIGHT, false))
+    }
+}
+
+impl CathodeRayTube {
+    pub fn pixels(&self) -> &Grid<bool> {
+        &self.0
+    }
+
+    /// Draws the pixel for `cycle_index`, lighting it if the sprite centred
+    /// on `x` covers it.
+    pub fn update_pixel(&mut self, x: i32, cycle_index: i32) {
+        let px = cycle_index as usize % (WIDTH * HEIGHT);
+        let (px_x, px_y) = (px % WIDTH, px / WIDTH);
+
+        let range = x - 1..=x + 1;
+
+        if range.contains(&(px_x as i32)) {
+            self.0[(px_x, px_y)] = true;
+        }
+    }
+}
+
+impl Display for CathodeRayTube {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.0.display_with(|on| if *on { '#' } else { '.' })
+        )
+    }
+}
+
+/// The device's CPU, with its one register
+#[derive(Debug, PartialEq, Eq)]
+pub struct CentralProcessingUnit {
+    x: i32,
+    cycle_index: i32,
+    signal_strength: i32,
+    tube: CathodeRayTube,
+}
+
+impl Default for CentralProcessingUnit {
+    fn default() -> Self {
+        Self {
+            x: 1,
+            cycle_index: 0,
+            signal_strength: 0,
+            tube: CathodeRayTube::default(),
+        }
+    }
+}
+
+impl CentralProcessingUnit {
+    /// The X register
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    /// How many cycles have finished
+    pub fn cycles(&self) -> i32 {
+        self.cycle_index
+    }
+
+    /// The sum of the signal strengths during the 20th, 60th, ... 220th
+    /// cycles, as far as they've been reached
+    pub fn signal_strength(&self) -> i32 {
+        self.signal_strength
+    }
+
+    pub fn tube(&self) -> &CathodeRayTube {
+        &self.tube
+    }
+
+    /// Runs a single cycle.
+    pub fn cycle(&mut self) {
+        self.tube.update_pixel(self.x, self.cycle_index);
+
+        let cycle_number = self.cycle_index + 1;
+        if cycle_number <= 220 && (cycle_number - 20) % 40 == 0 {
+            self.signal_strength += cycle_number * self.x;
+        }
+
+        self.cycle_index += 1;
+    }
+
+    /// Runs the cycle numbered `step` of a program, giving the instruction
+    /// it's part of, or `None` once the program is over. An `addx` changes X
+    /// at the end of its second cycle.
+    pub fn run_cycle(
+        &mut self,
+        program: &mut Schedule<Instruction>,
+        step: usize,
+    ) -> Option<Instruction> {
+        let (_, &instruction, cycle) = program.at(step)?;
+
+        self.cycle();
+        if let (Instruction::AddX(v), 1) = (instruction, cycle) {
+            self.x += v;
+        }
+
+        Some(instruction)
+    }
+
+    /// Runs all of `code`, calling `each_cycle` after every cycle.
+    pub fn execute_code(
+        &mut self,
+        code: &[Instruction],
+        mut each_cycle: impl FnMut(&Self, Instruction),
+    ) -> Result<()> {
+        let mut program = Schedule::new(code, Instruction::cycles);
+
+        checkpoint::finish(self, |cpu, step| {
+            let instruction = cpu.run_cycle(&mut program, step);
+            if let Some(instruction) = instruction {
+                each_cycle(cpu, instruction);
+            }
+            Ok(instruction.is_some())
+        })
+    }
+
+    /// Whether the pixel at `(x, y)` is lit, or `None` if the beam hasn't
+    /// reached it yet.
+    fn pixel(&self, x: usize, y: usize) -> Option<bool> {
+        let drawn = self.cycle_index as usize;
+        (drawn >= WIDTH * HEIGHT || y * WIDTH + x < drawn).then(|| self.tube.0[(x, y)])
+    }
+
+    /// The screen as far as the beam has drawn it, with the sprite
+    /// underneath.
+    fn draw(&self) -> String {
+        let mut view = String::new();
+
+        for y in 0..HEIGHT {
+            view.extend((0..WIDTH).map(|x| match self.pixel(x, y) {
+                None => ' ',
+                Some(true) => '#',
+                Some(false) => '.',
+            }));
+            view.push('\n');
+        }
+
+        view.push('\n');
+        view.extend(
+            (0..WIDTH as i32).map(|x| match (self.x - 1..=self.x + 1).contains(&x) {
+                true => '#',
+                false => ' ',
+            }),
+        );
+
+        view
+    }
+
+    /// The screen as far as the beam has drawn it, glowing green where it's
+    /// lit.
+    fn image(&self) -> Image {
+        let screen = Grid::from_fn(WIDTH, HEIGHT, |(x, y)| match self.pixel(x, y) {
+            None => Colour::BLACK,
+            Some(true) => Colour::rgb(120, 255, 120),
+            Some(false) => Colour::rgb(20, 50, 20),
+        });
+
+        Image::from_colours(&screen)
+    }
+}
+
+/// The registers and counters, then a `Row` line for each row of the
+/// screen.
+impl State for CentralProcessingUnit {
+    fn save(&self) -> String {
+        let mut state = format!(
+            "X: {}\nCycles: {}\nSignal strength: {}\n",
+            self.x, self.cycle_index, self.signal_strength
+        );
+        for row in self.tube.to_string().lines() {
+            state.push_str(&format!("Row: {row}\n"));
+        }
+
+        state
+    }
+
+    fn restore(s: Scanner) -> Result<Self> {
+        let mut lines = s.lines();
+        let mut field = |key: &str| {
+            lines
+                .next()
+                .ok_or_else(|| s.error_at_end(format!("Expected a '{key}' line")))?
+                .field(key)
+        };
+
+        let mut cpu = Self {
+            x: field("X")?.parse_all(Scanner::int)?,
+            cycle_index: field("Cycles")?.parse_all(Scanner::int)?,
+            signal_strength: field("Signal strength")?.parse_all(Scanner::int)?,
+            tube: CathodeRayTube::default(),
+        };
+
+        for y in 0..HEIGHT {
+            let row = field("Row")?;
+            let pixels = row.rest().chars().collect::<Vec<_>>();
+            if pixels.len() != WIDTH || pixels.iter().any(|pixel| !"#.".contains(*pixel)) {
+                return Err(row.error_at(
+                    row.rest(),
+                    format!("Expected a row of {WIDTH} '#' and '.' pixels"),
+                ));
+            }
+            for (x, pixel) in pixels.into_iter().enumerate() {
+                cpu.tube.0[(x, y)] = pixel == '#';
+            }
+        }
+        if let Some(extra) = lines.next() {
+            return Err(extra.error_at(extra.rest(), "Unexpected line after the screen"));
+        }
+
+        Ok(cpu)
+    }
+}
+
+/// `part`'s answer, from the CPU once the program's over
+fn answer(cpu: &CentralProcessingUnit, part: Part) -> Answer {
+    match part {
+        Part::One => cpu.signal_strength.into(),
+        Part::Two => cpu.tube.to_string().into(),
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<Instruction>;
+
+    fn parse(data: &str) -> Result<Self::Parsed> {
+        Scanner::new(data)
+            .lines()
+            .map(|line| line.parse_all(Instruction::scan))
+            .collect()
+    }
+
+    fn part_one(code: &Self::Parsed) -> Result<Answer> {
+        let mut cpu = CentralProcessingUnit::default();
+
+        cpu.execute_code(code, |_, _| {})?;
+
+        Ok(answer(&cpu, Part::One))
+    }
+
+    fn part_two(code: &Self::Parsed) -> Result<Answer> {
+        let mut cpu = CentralProcessingUnit::default();
+
+        cpu.execute_code(code, |_, _| {})?;
+
+        Ok(answer(&cpu, Part::Two))
+    }
+
+    /// The beam drawing the screen, one pixel a cycle.
+    fn animate(code: &Self::Parsed, part: Part, animation: &mut Animation) -> Result<Answer> {
+        let mut cpu = CentralProcessingUnit::default();
+
+        cpu.execute_code(code, |cpu, instruction| {
+            if animation.drawing() {
+                animation.frame(
+                    format_args!("Cycle {}: {instruction:?}, X = {}", cpu.cycles(), cpu.x()),
+                    cpu.draw(),
+                );
+            }
+        })?;
+
+        Ok(answer(&cpu, part))
+    }
+
+    /// The screen after every cycle, as the beam draws it.
+    fn render(code: &Self::Parsed, _part: Part) -> Result<Vec<Image>> {
+        let mut cpu = CentralProcessingUnit::default();
+        let mut frames = vec![];
+
+        cpu.execute_code(code, |cpu, _| frames.push(cpu.image()))?;
+
+        Ok(frames)
+    }
+
+    /// A step is one cycle, so a checkpoint can land half way through an
+    /// `addx`.
+    fn simulate(
+        code: &Self::Parsed,
+        part: Part,
+        from: Option<Saved>,
+        until: Option<usize>,
+    ) -> Result<Progress> {
+        let mut program = Schedule::new(code, Instruction::cycles);
+
+        checkpoint::run(
+            from,
+            CentralProcessingUnit::default,
+            until,
+            |cpu, step| Ok(cpu.run_cycle(&mut program, step).is_some()),
+            |cpu| answer(cpu, part),
+        )
+    }
+
+    /// A program that keeps the sprite somewhere on the screen.
+    fn generate(rng: &mut Rng, out: &mut Output) -> std::io::Result<()> {
+        let mut x = 1;
+        loop {
+            let value = rng.between(-10, 10);
+            if rng.chance(1, 3) || !(-1..=40).contains(&(x + value)) {
+                writeln!(out, "noop")?;
+            } else {
+                x += value;
+                writeln!(out, "addx {value}")?;
+            }
+            if out.full() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::{assert_snapshot, checkpoint::State, Result, Scanner, Solution};
+
+    use super::{CentralProcessingUnit, Puzzle, DAY};
+
+    const LARGER: &str = include_str!("../examples/larger.txt");
+
+    #[test]
+    fn renders() -> Result<()> {
+        let code = Puzzle::parse(LARGER)?;
+        let mut cpu = CentralProcessingUnit::default();
+
+        cpu.execute_code(&code[..60], |_, _| {})?;
+        assert_snapshot!("part_way", cpu.draw());
+
+        cpu.execute_code(&code[60..], |_, _| {})?;
+        assert_snapshot!("tube", cpu.tube());
+
+        Ok(())
+    }
+
+    #[test]
+    fn state_round_trips() -> Result<()> {
+        let code = Puzzle::parse(LARGER)?;
+        let mut cpu = CentralProcessingUnit::default();
+        cpu.execute_code(&code[..60], |_, _| {})?;
+
+        let state = cpu.save();
+        assert!(state.starts_with("X: 18\nCycles: 100\nSignal strength: 3360\nRow: ##..##"));
+        assert_eq!(CentralProcessingUnit::restore(Scanner::new(&state))?, cpu);
+        assert!(CentralProcessingUnit::restore(Scanner::new("X: 1\nCycles: 0\n")).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn resumes() {
+        if let Err(err) = common::examples::check_resumed(&DAY) {
+            panic!("{}", err.report());
+        }
+    }
+}
@@ -0,0 +1,708 @@
+//! Day 11: Monkey in the Middle. [`Monkeys`] inspect and throw items
+//! between themselves, round after round, while worry about each item grows.
+
+use std::{collections::VecDeque, fmt::Display, io::Write};
+
+use common::{
+    checkpoint::{self, Progress, Saved, State},
+    *,
+};
+
+pub const DAY: Day =
+    Day::new::<Puzzle>(2022, 11, "monkey_in_the_middle", env!("CARGO_MANIFEST_DIR"));
+
+common::example_tests!(DAY);
+
+/// A worry level, or a count of inspections
+pub type Number = u64;
+
+#[derive(Debug, PartialEq, Clone)]
+enum Operand {
+    Num(Number),
+    Old,
+}
+
+impl TryFrom<&str> for Operand {
+    type Error = Failure;
+
+    fn try_from(operand_token: &str) -> Result<Self, Self::Error> {
+        let operand = match operand_token {
+            "old" => Self::Old,
+            num_token => Self::Num(
+                num_token
+                    .parse()
+                    .map_err(|err| e!("Couldn't parse num token: {err}"))?,
+            ),
+        };
+
+        Ok(operand)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Operation {
+    Add(Operand),
+    Multiply(Operand),
+}
+
+impl Operation {
+    fn scan(s: &mut Scanner) -> Result<Self> {
+        s.field("Operation")?.parse_all(|s| {
+            s.literal("new = old ")?;
+            let operator = s.word()?;
+            s.literal(" ")?;
+            let operand = s.word_as(Operand::try_from)?;
+
+            let operation = match operator {
+                "+" => Self::Add(operand),
+                "*" => Self::Multiply(operand),
+                op => return Err(s.error_at(op, format!("Found unrecognised operator: {op}"))),
+            };
+
+            Ok(operation)
+        })
+    }
+}
+
+/// The way the input writes it, `new = old <operator> <operand>`
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (operator, operand) = match self {
+            Operation::Add(operand) => ('+', operand),
+            Operation::Multiply(operand) => ('*', operand),
+        };
+
+        match operand {
+            Operand::Num(num) => write!(f, "new = old {operator} {num}"),
+            Operand::Old => write!(f, "new = old {operator} old"),
+        }
+    }
+}
+
+impl TryFrom<&str> for Operation {
+    type Error = Failure;
+
+    fn try_from(string: &str) -> Result<Self> {
+        Scanner::new(string).parse_all(Operation::scan)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Monkey {
+    items: VecDeque<Number>,
+    operation: Operation,
+    divisor: Number,
+    catchers: (usize, usize),
+    items_inspected: Number,
+}
+
+impl Monkey {
+    fn scan(block: Scanner) -> Result<Self> {
+        Self::scan_as(block, false)
+    }
+
+    /// Reads a monkey from the input, or from a checkpoint when `saved`. A
+    /// saved monkey's items can run out, and are followed by how many it has
+    /// inspected.
+    fn scan_as(block: Scanner, saved: bool) -> Result<Self> {
+        let mut lines = block.lines();
+        let mut line = |what: &str| {
+            lines
+                .next()
+                .ok_or_else(|| block.error_at_end(format!("Expected a '{what}' line")))
+        };
+
+        line("Monkey")?.parse_all(|s| {
+            s.literal("Monkey ")?;
+            s.int::<usize>()?;
+            s.literal(":")
+        })?;
+
+        let items_key = if saved { "Items" } else { "Starting items" };
+        let items = line(items_key)?.parse_all(|s| {
+            let items = s.field(items_key)?;
+            if saved && items.is_empty() {
+                return Ok(vec![]);
+            }
+            items.parse_all(|s| s.separated(", ", Scanner::int))
+        })?;
+
+        let items_inspected = match saved {
+            true => {
+                line("Inspected")?.parse_all(|s| s.field("Inspected")?.parse_all(Scanner::int))?
+            }
+            false => 0,
+        };
+
+        let operation = line("Operation")?.parse_all(Operation::scan)?;
+
+        let divisor = line("Test")?.parse_all(|s| {
+            s.field("Test")?.parse_all(|s| {
+                s.literal("divisible by ")?;
+                s.spanned(|s| match s.int()? {
+                    0 => Err(Failure::validation("Can't test divisibility by 0")),
+                    divisor => Ok(divisor),
+                })
+            })
+        })?;
+
+        let mut catcher = |condition: &str| {
+            line(condition)?.parse_all(|s| {
+                s.field(condition)?.parse_all(|s| {
+                    s.literal("throw to monkey ")?;
+                    s.int()
+                })
+            })
+        };
+        let catchers = (catcher("If true")?, catcher("If false")?);
+
+        if let Some(extra) = lines.next() {
+            return Err(extra.error_at(extra.rest(), "Unexpected line after a monkey's catchers"));
+        }
+
+        let monkey = Self {
+            items: VecDeque::from(items),
+            operation,
+            divisor,
+            catchers,
+            items_inspected,
+        };
+
+        Ok(monkey)
+    }
+}
+
+/// How worry levels are kept manageable after each inspection
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Relief {
+    /// Worry is divided by three because the item wasn't damaged
+    DivideByThree,
+    /// Worry is only reduced modulo the product of every monkey's divisor
+    Modulo(Number),
+}
+
+impl Monkey {
+    fn throw_item(&mut self, relief: Relief) -> Option<(usize, Number)> {
+        self.items.pop_front().map(|item| {
+            let item = match self.operation {
+                Operation::Add(Operand::Num(num)) => item + num,
+                Operation::Add(Operand::Old) => item + item,
+                Operation::Multiply(Operand::Num(num)) => item * num,
+                Operation::Multiply(Operand::Old) => item * item,
+            };
+            let item = match relief {
+                Relief::DivideByThree => item / 3,
+                Relief::Modulo(product) => item % product,
+            };
+            let throw = if item.is_multiple_of(self.divisor) {
+                (self.catchers.0, item)
+            } else {
+                (self.catchers.1, item)
+            };
+
+            self.items_inspected += 1;
+            throw
+        })
+    }
+
+    fn catch_item(&mut self, item: Number) {
+        self.items.push_back(item);
+    }
+}
+
+/// Every monkey, in order, and how they keep worry in check
+#[derive(Debug, Clone)]
+pub struct Monkeys {
+    barrel: Vec<Monkey>,
+    relief: Relief,
+}
+
+impl TryFrom<&str> for Monkeys {
+    type Error = Failure;
+
+    fn try_from(string: &str) -> Result<Self, Self::Error> {
+        let monkeys = Scanner::new(string)
+            .blocks()
+            .map(Monkey::scan)
+            .collect::<Result<Vec<_>>>()?;
+        let product = monkeys.iter().map(|m| m.divisor).product();
+        Ok(Self {
+            barrel: monkeys,
+            relief: Relief::Modulo(product),
+        })
+    }
+}
+
+impl Monkeys {
+    /// Starts off relieved only by the modulo of every divisor, as in part two.
+    pub fn relief(&self) -> Relief {
+        self.relief
+    }
+
+    pub fn set_relief(&mut self, relief: Relief) {
+        self.relief = relief;
+    }
+
+    /// The worry levels of the items each monkey holds, next to be thrown
+    /// first.
+    pub fn items(&self) -> impl Iterator<Item = &VecDeque<Number>> {
+        self.barrel.iter().map(|monkey| &monkey.items)
+    }
+
+    /// How many items each monkey has inspected so far.
+    pub fn items_inspected(&self) -> impl Iterator<Item = Number> + '_ {
+        self.barrel.iter().map(|monkey| monkey.items_inspected)
+    }
+
+    /// Lets each monkey in turn throw every item it holds.
+    pub fn round_of_monkey_business(mut self) -> Result<Self> {
+        self.round_shown(None)?;
+        Ok(self)
+    }
+
+    /// The product of the two largest numbers of items inspected so far.
+    pub fn level_of_monkey_business(&self) -> Number {
+        let mut inspected = self.items_inspected().collect::<Vec<_>>();
+        inspected.sort();
+
+        inspected.into_iter().rev().take(2).product()
+    }
+
+    fn round_shown(&mut self, mut animation: Option<&mut Animation>) -> Result<()> {
+        for i in 0..self.barrel.len() {
+            while let Some((index, item)) = self.barrel[i].throw_item(self.relief) {
+                if index == i {
+                    return Err(Failure::simulation(format!(
+                        "Monkey {i} threw an item to itself"
+                    )));
+                }
+                self.barrel
+                    .get_mut(index)
+                    .ok_or_else(|| {
+                        Failure::simulation(format!(
+                            "Monkey {i} threw an item to non-existent monkey {index}"
+                        ))
+                    })?
+                    .catch_item(item);
+
+                if let Some(animation) = animation.as_deref_mut() {
+                    animation.frame(
+                        format_args!("Monkey {i} throws an item worth {item} to monkey {index}"),
+                        &*self,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Each monkey's inspections so far and the items it holds, one to a line.
+impl Display for Monkeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, monkey) in self.barrel.iter().enumerate() {
+            let items = monkey
+                .items
+                .iter()
+                .map(Number::to_string)
+                .collect::<Vec<_>>();
+            writeln!(
+                f,
+                "Monkey {i} ({} inspected): {}",
+                monkey.items_inspected,
+                items.join(", ")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// How worry is relieved, then each monkey as the input describes it, with
+/// the items it holds now and how many it has inspected.
+impl State for Monkeys {
+    fn save(&self) -> String {
+        let mut state = match self.relief {
+            Relief::DivideByThree => "Relief: divide by three\n".to_owned(),
+            Relief::Modulo(product) => format!("Relief: modulo {product}\n"),
+        };
+
+        for (i, monkey) in self.barrel.iter().enumerate() {
+            let items = monkey
+                .items
+                .iter()
+                .map(Number::to_string)
+                .collect::<Vec<_>>();
+            let lines = [
+                format!("Monkey {i}:"),
+                format!("  Items: {}", items.join(", "))
+                    .trim_end()
+                    .to_owned(),
+                format!("  Inspected: {}", monkey.items_inspected),
+                format!("  Operation: {}", monkey.operation),
+                format!("  Test: divisible by {}", monkey.divisor),
+                format!("    If true: throw to monkey {}", monkey.catchers.0),
+                format!("    If false: throw to monkey {}", monkey.catchers.1),
+            ];
+
+            state.push('\n');
+            for line in lines {
+                state.push_str(&line);
+                state.push('\n');
+            }
+        }
+
+        state
+    }
+
+    fn restore(s: Scanner) -> Result<Self> {
+        let mut blocks = s.blocks();
+        let relief = blocks
+            .next()
+            .ok_or_else(|| s.error_at_end("Expected a 'Relief' line"))?
+            .parse_all(|s| {
+                let mut relief = s.field("Relief")?;
+                if relief.try_literal("divide by three") {
+                    return relief.parse_all(|_| Ok(Relief::DivideByThree));
+                }
+                relief.parse_all(|s| {
+                    s.literal("modulo ")?;
+                    s.spanned(|s| match s.int()? {
+                        0 => Err(Failure::validation("Can't reduce worry modulo 0")),
+                        product => Ok(Relief::Modulo(product)),
+                    })
+                })
+            })?;
+        let barrel = blocks
+            .map(|block| Monkey::scan_as(block, true))
+            .collect::<Result<_>>()?;
+
+        Ok(Self { barrel, relief })
+    }
+}
+
+/// The product of the two largest numbers of items inspected, after
+/// `rounds` rounds.
+pub fn monkey_business(monkeys: Monkeys, rounds: usize) -> Result<Number> {
+    monkey_business_shown(monkeys, rounds, None)
+}
+
+fn monkey_business_shown(
+    mut monkeys: Monkeys,
+    rounds: usize,
+    mut animation: Option<&mut Animation>,
+) -> Result<Number> {
+    checkpoint::finish(&mut monkeys, |monkeys, round| {
+        play_round(monkeys, round, rounds, animation.as_deref_mut())
+    })?;
+
+    Ok(monkeys.level_of_monkey_business())
+}
+
+/// Plays the round numbered `round`, or returns `false` once all `rounds`
+/// have been played.
+fn play_round(
+    monkeys: &mut Monkeys,
+    round: usize,
+    rounds: usize,
+    mut animation: Option<&mut Animation>,
+) -> Result<bool> {
+    if round >= rounds {
+        return Ok(false);
+    }
+    if let Some(animation) = animation.as_deref_mut() {
+        animation.frame(format_args!("Round {} of {rounds}", round + 1), &*monkeys);
+    }
+    monkeys.round_shown(animation)?;
+
+    Ok(true)
+}
+
+/// The monkeys as `part` starts them off, and how many rounds it lasts
+fn start(monkeys: &Monkeys, part: Part) -> (Monkeys, usize) {
+    let mut monkeys = monkeys.clone();
+    match part {
+        Part::One => {
+            monkeys.set_relief(Relief::DivideByThree);
+            (monkeys, 20)
+        }
+        Part::Two => (monkeys, 10_000),
+    }
+}
+
+/// Distinct divisors whose product, times the largest operand, fits in a
+/// [`Number`]
+const DIVISORS: [Number; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Monkeys;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Monkeys::try_from(input)
+    }
+
+    fn part_one(monkeys: &Self::Parsed) -> Result<Answer> {
+        let mut monkeys = monkeys.clone();
+        monkeys.set_relief(Relief::DivideByThree);
+
+        Ok(monkey_business(monkeys, 20)?.into())
+    }
+
+    fn part_two(monkeys: &Self::Parsed) -> Result<Answer> {
+        Ok(monkey_business(monkeys.clone(), 10_000)?.into())
+    }
+
+    /// Items being thrown from monkey to monkey, one throw at a time.
+    fn animate(monkeys: &Self::Parsed, part: Part, animation: &mut Animation) -> Result<Answer> {
+        let (monkeys, rounds) = start(monkeys, part);
+
+        Ok(monkey_business_shown(monkeys, rounds, Some(animation))?.into())
+    }
+
+    /// A step is one round.
+    fn simulate(
+        monkeys: &Self::Parsed,
+        part: Part,
+        from: Option<Saved>,
+        until: Option<usize>,
+    ) -> Result<Progress> {
+        let (monkeys, rounds) = start(monkeys, part);
+
+        checkpoint::run(
+            from,
+            || monkeys,
+            until,
+            |monkeys, round| play_round(monkeys, round, rounds, None),
+            |monkeys| monkeys.level_of_monkey_business().into(),
+        )
+    }
+
+    /// Up to fifteen monkeys sharing about `out.size` bytes of items. The
+    /// first monkey throws to later ones, and every other monkey throws to
+    /// earlier ones, so no item is inspected more than twice a round and
+    /// worry stays within a [`Number`] for all of part one.
+    fn generate(rng: &mut Rng, out: &mut Output) -> std::io::Result<()> {
+        let count = (out.size / 200).clamp(2, DIVISORS.len());
+        let items = (out.size.saturating_sub(count * 170) / (count * 4)).max(1);
+        let mut divisors = DIVISORS[..count].to_vec();
+        rng.shuffle(&mut divisors);
+
+        for (i, divisor) in divisors.into_iter().enumerate() {
+            if i > 0 {
+                writeln!(out)?;
+            }
+
+            let items = (0..rng.between(1, 2 * items as i64 - 1))
+                .map(|_| rng.between(40, 99).to_string())
+                .collect::<Vec<_>>();
+            let operation = match rng.below(3) {
+                0 => format!("+ {}", rng.between(1, 9)),
+                1 => format!("* {}", rng.between(2, 7)),
+                _ => "+ old".to_owned(),
+            };
+            let mut catcher = || match i {
+                0 => rng.between(1, count as i64 - 1),
+                _ => rng.between(0, i as i64 - 1),
+            };
+            let catchers = (catcher(), catcher());
+
+            writeln!(out, "Monkey {i}:")?;
+            writeln!(out, "  Starting items: {}", items.join(", "))?;
+            writeln!(out, "  Operation: new = old {operation}")?;
+            writeln!(out, "  Test: divisible by {divisor}")?;
+            writeln!(out, "    If true: throw to monkey {}", catchers.0)?;
+            writeln!(out, "    If false: throw to monkey {}", catchers.1)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::VecDeque;
+
+    use common::{checkpoint::State, Scanner};
+
+    use crate::{Monkey, Monkeys, Operand, Operation, Relief, Result, DAY};
+
+    #[test]
+    fn parse_operation() -> Result<()> {
+        let operation_string = "  Operation: new = old * 13";
+        let operation = Operation::try_from(operation_string)?;
+
+        assert_eq!(Operation::Multiply(Operand::Num(13)), operation);
+
+        let operation_string = "  Operation: new = old + 4";
+        let operation = Operation::try_from(operation_string)?;
+
+        assert_eq!(Operation::Add(Operand::Num(4)), operation);
+
+        let operation_string = "  Operation: new = old * old";
+        let operation = Operation::try_from(operation_string)?;
+
+        assert_eq!(Operation::Multiply(Operand::Old), operation);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_monkeys() -> Result<()> {
+        let string = "Monkey 1:
+  Starting items: 73, 99, 55, 54, 88, 50, 55
+  Operation: new = old + 4
+  Test: divisible by 17
+    If true: throw to monkey 2
+    If false: throw to monkey 6
+
+Monkey 2:
+  Starting items: 67, 98
+  Operation: new = old * 11
+  Test: divisible by 5
+    If true: throw to monkey 6
+    If false: throw to monkey 5";
+        let monkeys = Scanner::new(string)
+            .blocks()
+            .map(Monkey::scan)
+            .collect::<Result<Vec<_>>>()?;
+
+        let expected = [
+            Monkey {
+                items: VecDeque::from_iter([73, 99, 55, 54, 88, 50, 55]),
+                operation: Operation::Add(Operand::Num(4)),
+                divisor: 17,
+                catchers: (2, 6),
+                items_inspected: 0,
+            },
+            Monkey {
+                items: VecDeque::from_iter([67, 98]),
+                operation: Operation::Multiply(Operand::Num(11)),
+                divisor: 5,
+                catchers: (6, 5),
+                items_inspected: 0,
+            },
+        ];
+
+        assert_eq!(monkeys[0], expected[0]);
+
+        assert_eq!(monkeys[1], expected[1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn monkey_throw_items() -> Result<()> {
+        let mut monkey = Monkey {
+            items: VecDeque::from([79, 60, 97]),
+            operation: Operation::Multiply(Operand::Old),
+            divisor: 13,
+            catchers: (1, 3),
+            items_inspected: 0,
+        };
+
+        let items = [
+            monkey.throw_item(Relief::Modulo(monkey.divisor)),
+            monkey.throw_item(Relief::Modulo(monkey.divisor)),
+            monkey.throw_item(Relief::Modulo(monkey.divisor)),
+        ];
+
+        assert_eq!(&[Some((3, 1)), Some((3, 12)), Some((3, 10))], &items[..]);
+
+        Ok(())
+    }
+
+    const EXAMPLE: &str = "Monkey 0:
+Starting items: 79, 98
+Operation: new = old * 19
+Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+Starting items: 54, 65, 75, 74
+Operation: new = old + 6
+Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+Starting items: 79, 60, 97
+Operation: new = old * old
+Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+Starting items: 74
+Operation: new = old + 3
+Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+    #[test]
+    fn monkey_business_from_input() -> Result<()> {
+        let monkeys = Monkeys::try_from(EXAMPLE)?;
+
+        let monkey_business = super::monkey_business(monkeys, 10_000)?;
+
+        assert_eq!(2713310158, monkey_business);
+
+        Ok(())
+    }
+
+    #[test]
+    fn monkey_business_with_relief() -> Result<()> {
+        let monkeys = Monkeys {
+            relief: Relief::DivideByThree,
+            ..Monkeys::try_from(EXAMPLE)?
+        };
+
+        let monkey_business = super::monkey_business(monkeys, 20)?;
+
+        assert_eq!(10605, monkey_business);
+
+        Ok(())
+    }
+
+    #[test]
+    fn renders() -> Result<()> {
+        let monkeys = Monkeys {
+            relief: Relief::DivideByThree,
+            ..Monkeys::try_from(EXAMPLE)?
+        };
+
+        common::assert_snapshot!("round_one", monkeys.round_of_monkey_business()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn state_round_trips() -> Result<()> {
+        let monkeys = Monkeys::try_from(EXAMPLE)?.round_of_monkey_business()?;
+        let state = monkeys.save();
+
+        assert!(state.starts_with(
+            "Relief: modulo 96577\n\nMonkey 0:\n  Items: 60, 71, 81, 80\n  Inspected: 2\n  Operation: new = old * 19\n"
+        ));
+        assert!(state.contains("\nMonkey 2:\n  Items:\n  Inspected: 3\n"));
+        let restored = Monkeys::restore(Scanner::new(&state))?;
+        assert_eq!(restored.barrel, monkeys.barrel);
+        assert_eq!(restored.relief, monkeys.relief);
+
+        let failure =
+            Monkeys::restore(Scanner::new(&state.replace("modulo 96577", "modulo 0"))).unwrap_err();
+        assert_eq!(failure.kind(), "validation");
+
+        Ok(())
+    }
+
+    #[test]
+    fn resumes() {
+        if let Err(err) = common::examples::check_resumed(&DAY) {
+            panic!("{}", err.report());
+        }
+    }
+}
@@ -0,0 +1,416 @@
+//! Day 12: Hill Climbing Algorithm. A [`Map`] of elevations is searched for
+//! the shortest climb to the best signal.
+
+use std::{fmt::Display, io::Write};
+
+use common::{
+    geom::{Direction, UPoint},
+    image::{Colour, Gradient, Image},
+    search::{Bfs, Visits},
+    *,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(
+    2022,
+    12,
+    "hill_climbing_algorithm",
+    env!("CARGO_MANIFEST_DIR"),
+);
+
+common::example_tests!(DAY);
+
+/// One square of the heightmap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Square {
+    Elevation(u8),
+    Start,
+    End,
+}
+
+impl Square {
+    /// From 1 for `a` to 26 for `z`. The start is at `a` and the end at `z`.
+    pub fn elevation(&self) -> u8 {
+        match self {
+            Square::Elevation(elevation) => *elevation,
+            Square::Start => b'a' - 96,
+            Square::End => b'z' - 96,
+        }
+    }
+}
+
+/// The heightmap and where the climb starts
+pub struct Map {
+    grid: Grid<Square>,
+    start: UPoint,
+}
+
+impl Display for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ch = match self {
+            Square::Elevation(elevation) => (elevation + 96) as char,
+            Square::Start => 'S',
+            Square::End => 'E',
+        };
+        write!(f, "{ch}")
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+impl Map {
+    pub fn grid(&self) -> &Grid<Square> {
+        &self.grid
+    }
+
+    pub fn start(&self) -> UPoint {
+        self.start
+    }
+
+    /// Turns the map upside down and swaps the start and end, so that
+    /// climbing up it is like climbing down the original.
+    pub fn invert(&self) -> Self {
+        let grid = self.grid.map(|square| match square {
+            Square::Elevation(elevation) => {
+                Square::Elevation(Square::End.elevation() - elevation + 1)
+            }
+            Square::Start => Square::End,
+            Square::End => Square::Start,
+        });
+
+        Self {
+            start: grid
+                .position(|square| matches!(square, Square::Start))
+                .expect("Map must be inverted from a valid Map")
+                .into(),
+            grid,
+        }
+    }
+}
+
+impl TryFrom<&str> for Map {
+    type Error = Failure;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let grid = Grid::parse(value, |c| match c {
+            'S' => Ok(Square::Start),
+            'E' => Ok(Square::End),
+            'a'..='z' => Ok(Square::Elevation(c as u8 - 96)),
+            c => Err(Failure::parse(format!("Invalid square: {c:?}"))),
+        })?;
+
+        if grid
+            .position(|square| matches!(square, Square::End))
+            .is_none()
+        {
+            return Err(Failure::validation("Map did not contain an end point"));
+        }
+
+        Ok(Self {
+            start: grid
+                .position(|square| matches!(square, Square::Start))
+                .ok_or_else(|| Failure::validation("Map did not contain a start point"))?
+                .into(),
+            grid,
+        })
+    }
+}
+
+fn arrow(direction: Direction) -> char {
+    match direction {
+        Direction::Up => '↑',
+        Direction::Down => '↓',
+        Direction::Left => '←',
+        Direction::Right => '→',
+    }
+}
+
+/// The direction that leads from `from` to the neighbouring `to`
+fn direction_between(from: UPoint, to: UPoint) -> Option<Direction> {
+    Direction::ALL
+        .into_iter()
+        .find(|direction| from.step(*direction) == Some(to))
+}
+
+/// How many steps it takes from the start to the nearest square that
+/// `is_goal`, never climbing more than one higher at a time.
+pub fn shortest_path_length(map: &Map, is_goal: fn(&Square) -> bool) -> Result<usize> {
+    Ok(shortest_path(map, is_goal, None)?.len() - 1)
+}
+
+/// The map with the squares the search has expanded filled in and the ones
+/// it's about to expand marked.
+fn draw_search<'a>(
+    map: &Map,
+    visited: impl Iterator<Item = &'a UPoint>,
+    frontier: impl Iterator<Item = &'a UPoint>,
+) -> String {
+    let mut view = map.grid.map(Square::to_string);
+    for position in visited {
+        view[*position] = "█".to_owned();
+    }
+    for position in frontier {
+        view[*position] = "░".to_owned();
+    }
+
+    view.to_string()
+}
+
+/// The squares from the start to the nearest one that `is_goal`, and
+/// everywhere the search reached before finding it.
+fn search(
+    map: &Map,
+    is_goal: fn(&Square) -> bool,
+    mut animation: Option<&mut Animation>,
+) -> Result<(Vec<UPoint>, Visits<UPoint>)> {
+    let mut search = Bfs::new([map.start], |position: &UPoint| {
+        let elevation = map.grid[*position].elevation();
+        position
+            .neighbours()
+            .filter(|target| {
+                map.grid
+                    .get(*target)
+                    .is_some_and(|target| target.elevation() <= elevation + 1)
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let end = loop {
+        let position = search
+            .next()
+            .ok_or_else(|| Failure::simulation("End not found"))?;
+
+        if let Some(animation) = animation.as_deref_mut().filter(|a| a.drawing()) {
+            animation.frame(
+                format_args!("Expanded ({}, {})", position.x, position.y),
+                draw_search(map, search.visited(), search.frontier()),
+            );
+        }
+
+        if is_goal(&map.grid[position]) {
+            break position;
+        }
+    };
+
+    let shortest_path = search
+        .path_to(&end)
+        .ok_or_else(|| e!("Ran out of directions!"))?;
+
+    Ok((shortest_path, search.into_visits()))
+}
+
+/// An arrow on every square the search reached, pointing back the way it
+/// came.
+fn search_map(map: &Map, visits: &Visits<UPoint>) -> String {
+    let mut search_map = Grid::new(map.grid.width(), map.grid.height(), None);
+    for (position, visit) in visits.iter() {
+        search_map[*position] = visit
+            .parent
+            .and_then(|parent| direction_between(*position, parent));
+    }
+
+    format!(
+        "{}",
+        search_map.display_with(|cell| cell.map_or('.', arrow))
+    )
+}
+
+/// The squares on `path` shaded in.
+fn path_visual(map: &Map, path: &[UPoint]) -> String {
+    let mut path_visual = Grid::new(map.grid.width(), map.grid.height(), false);
+    for position in path {
+        path_visual[*position] = true;
+    }
+
+    format!(
+        "{}",
+        path_visual.display_with(|cell| if *cell { "▓" } else { "░" })
+    )
+}
+
+/// Every square from the start to the nearest one that `is_goal`.
+fn shortest_path(
+    map: &Map,
+    is_goal: fn(&Square) -> bool,
+    animation: Option<&mut Animation>,
+) -> Result<Vec<UPoint>> {
+    let (shortest_path, visits) = search(map, is_goal, animation)?;
+
+    debug!("Map:\n{}", map);
+    if let Some(end) = shortest_path.last() {
+        debug!("End: ({}, {})", end.x, end.y);
+    }
+
+    if log::enabled(log::Level::Trace) {
+        trace!("Search map:\n{}", search_map(map, &visits));
+        trace!(
+            "Shortest path visual:\n{}",
+            path_visual(map, &shortest_path)
+        );
+        trace!(
+            "Shortest path: {}",
+            shortest_path
+                .windows(2)
+                .filter_map(|step| direction_between(step[0], step[1]))
+                .map(arrow)
+                .collect::<String>()
+        );
+    }
+
+    Ok(shortest_path)
+}
+
+/// Whether a square of an inverted map was at the lowest elevation before
+/// it was inverted.
+fn is_lowest(square: &Square) -> bool {
+    square.elevation() == Square::End.elevation()
+}
+
+/// The terrain, from green valleys to white peaks, with `path` over it.
+fn draw_path(map: &Map, path: &[UPoint]) -> Image {
+    let terrain = Gradient {
+        stops: vec![
+            Colour::rgb(30, 80, 40),
+            Colour::rgb(150, 120, 80),
+            Colour::rgb(240, 240, 250),
+        ],
+        min: Square::Start.elevation() as f64,
+        max: Square::End.elevation() as f64,
+    };
+    let mut image = Image::from_grid(&map.grid, &|square: &Square| {
+        terrain.at(square.elevation() as f64)
+    });
+
+    for position in path {
+        image.set(position.x, position.y, Colour::rgb(220, 30, 30));
+    }
+
+    image
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Map::try_from(input)
+    }
+
+    fn part_one(map: &Self::Parsed) -> Result<Answer> {
+        let length = shortest_path_length(map, |square| matches!(square, Square::End))?;
+
+        Ok(length.into())
+    }
+
+    /// Searches backwards from the end, on an inverted map, for the nearest
+    /// square at the lowest elevation
+    fn part_two(map: &Self::Parsed) -> Result<Answer> {
+        let map = map.invert();
+
+        let length = shortest_path_length(&map, is_lowest)?;
+
+        Ok(length.into())
+    }
+
+    /// The search spreading out over the map, one square at a time.
+    fn animate(map: &Self::Parsed, part: Part, animation: &mut Animation) -> Result<Answer> {
+        let path = match part {
+            Part::One => {
+                shortest_path(map, |square| matches!(square, Square::End), Some(animation))?
+            }
+            Part::Two => shortest_path(&map.invert(), is_lowest, Some(animation))?,
+        };
+
+        Ok((path.len() - 1).into())
+    }
+
+    /// The map with the shortest path drawn on it. Inverting the map leaves
+    /// every square where it was, so part two's path fits it too.
+    fn render(map: &Self::Parsed, part: Part) -> Result<Vec<Image>> {
+        let path = match part {
+            Part::One => shortest_path(map, |square| matches!(square, Square::End), None)?,
+            Part::Two => shortest_path(&map.invert(), is_lowest, None)?,
+        };
+
+        Ok(vec![draw_path(map, &path)])
+    }
+
+    /// Terrain rising from `S` in one corner to `E` in the other, with a
+    /// winding path between them that never climbs too steeply.
+    fn generate(rng: &mut Rng, out: &mut Output) -> std::io::Result<()> {
+        // Wide and tall enough for a path that climbs one step at a time
+        let side = ((out.size as f64).sqrt() as usize).max(14);
+        let length = 2 * side - 1;
+        let elevation = |steps: usize| (steps * 25 / (length - 1)) as u8;
+
+        let mut moves = [vec![true; side - 1], vec![false; side - 1]].concat();
+        rng.shuffle(&mut moves);
+        // The columns the path crosses on each row
+        let mut path = vec![(0, 0); side];
+        let (mut x, mut y) = (0, 0);
+        for down in moves {
+            if down {
+                y += 1;
+                path[y].0 = x;
+            } else {
+                x += 1;
+            }
+            path[y].1 = x;
+        }
+
+        for (y, &(from, to)) in path.iter().enumerate() {
+            let row = (0..side)
+                .map(|x| match (x, y) {
+                    (0, 0) => b'S',
+                    _ if (x, y) == (side - 1, side - 1) => b'E',
+                    _ if (from..=to).contains(&x) => b'a' + elevation(x + y),
+                    _ => b'a' + elevation(x + y).saturating_sub(rng.below(4) as u8),
+                })
+                .collect::<Vec<_>>();
+            out.write_all(&row)?;
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::{assert_snapshot, fuzz, normalize, Result};
+
+    use super::{path_visual, search, search_map, Map, Square, DAY};
+
+    const EXAMPLE: &str = include_str!("../examples/example.txt");
+
+    #[test]
+    fn renders() -> Result<()> {
+        let map = Map::try_from(normalize(EXAMPLE).as_ref())?;
+        let (path, visits) = search(&map, |square| matches!(square, Square::End), None)?;
+
+        assert_snapshot!("map", map);
+        assert_snapshot!("search_map", search_map(&map, &visits));
+        assert_snapshot!("path_visual", path_visual(&map, &path));
+
+        Ok(())
+    }
+
+    #[test]
+    fn map_round_trips() -> Result<()> {
+        let maps = fuzz::day_seeds(&DAY)?
+            .iter()
+            .map(|input| normalize(input).into_owned())
+            .collect::<Vec<_>>();
+
+        fuzz::check_round_trip(
+            &fuzz::dir(env!("CARGO_MANIFEST_DIR"), "map"),
+            &maps,
+            |input| Map::try_from(input),
+            Map::to_string,
+        )
+    }
+}
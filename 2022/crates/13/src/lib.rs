@@ -0,0 +1,113 @@
+//! Day 13: Distress Signal. Packets are nested lists of numbers, [`Oor`]s,
+//! compared element by element to put them in order.
+
+pub mod recursive_number_list;
+
+use std::io::Write;
+
+use common::*;
+
+pub const DAY: Day = Day::new::<Puzzle>(2022, 13, "distress_signal", env!("CARGO_MANIFEST_DIR"));
+
+common::example_tests!(DAY);
+
+/// One or a recursive list: a number, or a list of numbers and lists
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Oor<T> {
+    One(T),
+    RecursiveList(Vec<Oor<T>>),
+}
+
+fn generate_packet(rng: &mut Rng, depth: usize) -> String {
+    let items = (0..rng.below(5))
+        .map(|_| match depth < 4 && rng.chance(1, 3) {
+            true => generate_packet(rng, depth + 1),
+            false => rng.between(0, 10).to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    format!("[{}]", items.join(","))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    /// Pairs of packets, in the order they were received
+    type Parsed = Vec<(Oor<u8>, Oor<u8>)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Scanner::new(input)
+            .blocks()
+            .map(|section| {
+                let packets = section
+                    .lines()
+                    .map(|line| line.parse_all(recursive_number_list::scan_list))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let count = packets.len();
+                let Ok([left, right]) = <[_; 2]>::try_from(packets) else {
+                    return Err(section.error_at(
+                        section.rest(),
+                        format!("Expected a pair of packets, found {count}"),
+                    ));
+                };
+
+                Ok((Oor::RecursiveList(left), Oor::RecursiveList(right)))
+            })
+            .collect()
+    }
+
+    fn part_one(pairs: &Self::Parsed) -> Result<Answer> {
+        let ordered_indices_total = pairs
+            .iter()
+            .enumerate()
+            .filter(|(_, (left, right))| left < right)
+            .map(|(i, _)| i + 1)
+            .sum::<usize>();
+
+        Ok(ordered_indices_total.into())
+    }
+
+    fn part_two(pairs: &Self::Parsed) -> Result<Answer> {
+        let mut packets = pairs
+            .iter()
+            .flat_map(|(left, right)| [left.clone(), right.clone()])
+            .collect::<Vec<_>>();
+
+        let divider_packets = (
+            recursive_number_list::oor_from_str("[[2]]")?,
+            recursive_number_list::oor_from_str("[[6]]")?,
+        );
+
+        packets.push(divider_packets.0.clone());
+        packets.push(divider_packets.1.clone());
+
+        packets.sort();
+
+        let divider_packets = (
+            packets
+                .iter()
+                .position(|oor| *oor == divider_packets.0)
+                .expect("divider 1 must be in `packets`")
+                + 1,
+            packets
+                .iter()
+                .position(|oor| *oor == divider_packets.1)
+                .expect("divider 2 must be in `packets`")
+                + 1,
+        );
+
+        Ok((divider_packets.0 * divider_packets.1).into())
+    }
+
+    fn generate(rng: &mut Rng, out: &mut Output) -> std::io::Result<()> {
+        loop {
+            writeln!(out, "{}", generate_packet(rng, 0))?;
+            writeln!(out, "{}", generate_packet(rng, 0))?;
+            if out.full() {
+                return Ok(());
+            }
+            writeln!(out)?;
+        }
+    }
+}
@@ -1,9 +1,13 @@
+//! Reading packets, and the order they go in.
+
 use std::cmp::Ordering;
 
 use common::*;
 
 use super::Oor;
 
+// Packets are compared the way the puzzle describes, here, and `Ord` follows
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for Oor<u8> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         match (self, other) {
@@ -34,116 +38,44 @@ impl Ord for Oor<u8> {
     }
 }
 
-pub(super) fn oor_from_str(string: &str) -> Result<Oor<u8>> {
-    let oor = match string
-        .chars()
-        .next()
-        .ok_or_else(|| e!("Tried to parse an empty string as a recursive number list"))?
-    {
-        '[' => Oor::RecursiveList(from_str(string)?),
-        _ => Oor::One(
-            string
-                .parse()
-                .map_err(|err| e!("Couldn't parse string '{string}' as a u8: {err}"))?,
-        ),
-    };
-
-    Ok(oor)
-}
-
-fn split_csv_outside_brackets(csv: &str) -> Result<Vec<String>> {
-    let mut result = Vec::new();
-    let mut start = 0;
-    let mut brackets = 0;
-
-    for (i, c) in csv.chars().enumerate() {
-        match c {
-            '[' => brackets += 1,
-            ']' => brackets -= 1,
-            ',' => {
-                if brackets < 1 {
-                    let slice = csv
-                        .get(start..i)
-                        .ok_or_else(|| e!("Couldn't slice csv at {start}..{i}"))?;
-                    result.push(slice.to_owned());
-                    start = i + 1;
-                }
-            }
-            _ => (),
-        }
+/// Reads a number or a list.
+pub fn scan_oor(s: &mut Scanner) -> Result<Oor<u8>> {
+    match s.peek() {
+        Some('[') => Ok(Oor::RecursiveList(scan_list(s)?)),
+        _ => Ok(Oor::One(s.int()?)),
     }
+}
 
-    if brackets != 0 {
-        return Err(e!("Unbalanced brackets: {brackets}"));
+/// Reads a list, like `[1,[2,3]]`, giving its items.
+pub fn scan_list(s: &mut Scanner) -> Result<Vec<Oor<u8>>> {
+    s.literal("[")?;
+    if s.try_literal("]") {
+        return Ok(vec![]);
     }
+    let items = s.separated(",", scan_oor)?;
+    s.literal("]")?;
 
-    let slice = csv
-        .get(start..)
-        .ok_or_else(|| e!("Couldn't slice csv at {start}.."))?;
-    result.push(slice.to_owned());
-
-    Ok(result)
+    Ok(items)
 }
 
-pub(super) fn from_str(string: &str) -> Result<Vec<Oor<u8>>> {
-    let inner_list_string = string
-        .chars()
-        .enumerate()
-        .filter_map(|(i, ch)| {
-            if (i == 0 && ch == '[') || (i == string.len() - 1 && ch == ']') {
-                None
-            } else {
-                Some(ch)
-            }
-        })
-        .collect::<String>();
-
-    if inner_list_string.is_empty() {
-        return Ok(vec![]);
-    }
+/// Reads a whole string as a number or a list.
+pub fn oor_from_str(string: &str) -> Result<Oor<u8>> {
+    Scanner::new(string).parse_all(scan_oor)
+}
 
-    split_csv_outside_brackets(&inner_list_string)?
-        .into_iter()
-        .map(|s| oor_from_str(&s))
-        .collect()
+/// Reads a whole string as a list, giving its items.
+pub fn from_str(string: &str) -> Result<Vec<Oor<u8>>> {
+    Scanner::new(string).parse_all(scan_list)
 }
 
 #[cfg(test)]
 mod test {
     use std::cmp::Ordering;
 
-    use common::Result;
+    use common::{fuzz, lines, Result};
 
     use super::Oor;
 
-    #[test]
-    fn split_csv_outside_brackets() -> Result<()> {
-        let example = "1,[2,[3,[4,[5,6,0]]]],8,9";
-        let strings = super::split_csv_outside_brackets(example)?;
-
-        assert_eq!(&["1", "[2,[3,[4,[5,6,0]]]]", "8", "9"], strings.as_slice());
-
-        let example = "1,]2,]3,]4,]5,6,0[[[[,8,9";
-        let strings = super::split_csv_outside_brackets(example)?;
-
-        assert_eq!(
-            &["1", "]2", "]3", "]4", "]5", "6", "0[[[[", "8", "9"],
-            strings.as_slice()
-        );
-
-        let example = "2,[3,[4,[5,6,0]]]";
-        let strings = super::split_csv_outside_brackets(example)?;
-
-        assert_eq!(&["2", "[3,[4,[5,6,0]]]"], strings.as_slice());
-
-        let example = "[]";
-        let strings = super::split_csv_outside_brackets(example)?;
-
-        assert_eq!(&["[]"], strings.as_slice());
-
-        Ok(())
-    }
-
     #[test]
     fn from_str() -> Result<()> {
         let example = "[1,[2,[3,[4,[5,6,0]]]],8,9]";
@@ -169,6 +101,11 @@ mod test {
             oor.as_slice()
         );
 
+        assert!(super::from_str("[]")?.is_empty());
+        assert!(super::from_str("[1,,2]").is_err());
+        assert!(super::from_str("[1,[2]").is_err());
+        assert!(super::from_str("1").is_err());
+
         Ok(())
     }
 
@@ -211,4 +148,19 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn fuzz_from_str() -> Result<()> {
+        let packets = fuzz::day_seeds(&crate::DAY)?
+            .iter()
+            .flat_map(|input| lines(input).map(str::to_owned).collect::<Vec<_>>())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+
+        fuzz::check_parser(
+            &fuzz::dir(env!("CARGO_MANIFEST_DIR"), "recursive_number_list"),
+            &packets,
+            super::from_str,
+        )
+    }
 }
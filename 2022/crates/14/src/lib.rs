@@ -0,0 +1,150 @@
+//! Day 14: Regolith Reservoir. Reads the paths of rock that sand falls onto.
+//! The simulation itself isn't written yet.
+
+use std::io::Write;
+
+use common::{geom::Point, *};
+
+pub const DAY: Day = Day::new::<Puzzle>(2022, 14, "regolith_reservoir", env!("CARGO_MANIFEST_DIR"));
+
+common::example_tests!(DAY);
+
+// Not used until the simulation is written
+#[allow(dead_code)]
+#[derive(PartialEq, Debug)]
+struct AxisSpan {
+    length: u64,
+    /// `false` means vertical
+    horizontal: bool,
+}
+
+#[allow(dead_code)]
+fn extrude_collinear_points((a, b): (Point, Point)) -> Result<(Point, AxisSpan)> {
+    let dx = a.x.abs_diff(b.x);
+    let dy = a.y.abs_diff(b.y);
+
+    match (dx, dy) {
+        (0, 0) => Err(e!("Points are not collinear (they are the same)")),
+        (0, dy) => {
+            let axis_span = AxisSpan {
+                length: dy,
+                horizontal: false,
+            };
+            let point = if a.y < b.y { a } else { b };
+            Ok((point, axis_span))
+        }
+        (dx, 0) => {
+            let axis_span = AxisSpan {
+                length: dx,
+                horizontal: true,
+            };
+            let point = if a.x < b.x { a } else { b };
+            Ok((point, axis_span))
+        }
+        _ => Err(e!("Points are not collinear")),
+    }
+}
+
+fn scan_coordinate(s: &mut Scanner) -> Result<Point> {
+    let x = s.int()?;
+    s.literal(",")?;
+    let y = s.int()?;
+    Ok(Point::new(x, y))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    /// Each rock path as the list of points it passes through
+    type Parsed = Vec<Vec<Point>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        Scanner::new(input)
+            .lines()
+            .map(|line| line.parse_all(|s| s.separated(" -> ", scan_coordinate)))
+            .collect()
+    }
+
+    fn part_one(paths: &Self::Parsed) -> Result<Answer> {
+        trace!("{:#?}", paths);
+
+        Err(e!("Regolith Reservoir isn't solved yet"))
+    }
+
+    fn part_two(_paths: &Self::Parsed) -> Result<Answer> {
+        Err(e!("Regolith Reservoir isn't solved yet"))
+    }
+
+    /// Paths of rock below the sand's source, spreading wider as there are
+    /// more of them.
+    fn generate(rng: &mut Rng, out: &mut Output) -> std::io::Result<()> {
+        let spread = (out.size as f64).sqrt() as i64 / 2 + 20;
+
+        loop {
+            let mut x = rng.between(500 - spread, 500 + spread);
+            let mut y = rng.between(2, spread + 2);
+            let mut points = vec![format!("{x},{y}")];
+
+            for turn in 0..rng.between(1, 4) {
+                let step = rng.between(1, 8) * if rng.chance(1, 2) { 1 } else { -1 };
+                if turn % 2 == 0 {
+                    x += step;
+                } else if y + step < 2 {
+                    y -= step;
+                } else {
+                    y += step;
+                }
+                points.push(format!("{x},{y}"));
+            }
+
+            writeln!(out, "{}", points.join(" -> "))?;
+            if out.full() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::geom::Point;
+
+    use crate::AxisSpan;
+
+    use super::Result;
+
+    #[test]
+    fn extrude_collinear_points() -> Result<()> {
+        let result = super::extrude_collinear_points((Point::new(12, 13), Point::new(12, 12)))?;
+
+        assert_eq!(
+            (
+                Point::new(12, 12),
+                AxisSpan {
+                    length: 1,
+                    horizontal: false
+                }
+            ),
+            result
+        );
+
+        let result = super::extrude_collinear_points((Point::new(1, 13), Point::new(20, 13)))?;
+
+        assert_eq!(
+            (
+                Point::new(1, 13),
+                AxisSpan {
+                    length: 19,
+                    horizontal: true
+                }
+            ),
+            result
+        );
+
+        let result = super::extrude_collinear_points((Point::new(1, 1), Point::new(1, 1)));
+
+        assert!(result.is_err());
+
+        Ok(())
+    }
+}
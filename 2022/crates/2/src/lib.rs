@@ -0,0 +1,169 @@
+//! Day 2: Rock Paper Scissors. Scores a strategy guide, read first as the
+//! hands to play and then as the outcomes to aim for.
+
+use std::io::Write;
+
+use common::*;
+
+pub const DAY: Day = Day::new::<Puzzle>(2022, 2, "rock_paper_scissors", env!("CARGO_MANIFEST_DIR"));
+
+common::example_tests!(DAY);
+
+/// The guide's second column, which each part reads differently
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Column {
+    X,
+    Y,
+    Z,
+}
+
+impl TryFrom<&str> for Column {
+    type Error = Failure;
+
+    fn try_from(column: &str) -> Result<Self> {
+        match column {
+            "X" => Ok(Column::X),
+            "Y" => Ok(Column::Y),
+            "Z" => Ok(Column::Z),
+            _ => Err(Failure::parse(format!(
+                "Invalid second column: {column}, expected X, Y or Z"
+            ))),
+        }
+    }
+}
+
+/// The score for the outcome the guide's second column asks for.
+pub fn score_outcome(outcome: Column) -> u32 {
+    match outcome {
+        Column::X => 0,
+        Column::Y => 3,
+        Column::Z => 6,
+    }
+}
+
+/// A hand, worth its discriminant when played
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Hand {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+impl Hand {
+    /// Reads the guide's second column as a hand to play.
+    pub fn from_response(response: Column) -> Hand {
+        match response {
+            Column::X => Hand::Rock,
+            Column::Y => Hand::Paper,
+            Column::Z => Hand::Scissors,
+        }
+    }
+
+    /// 0 for a loss against `opp`, 3 for a draw and 6 for a win.
+    pub fn score_against(&self, opp: &Hand) -> u32 {
+        [0, 3, 6]
+            .into_iter()
+            .find(|outcome| opp.tsniaga_erocs(*outcome).ok().as_ref() == Some(self))
+            .expect("every pair of hands has an outcome")
+    }
+
+    /// The hand that gets the `outcome` score against this one: `score_against`
+    /// backwards.
+    pub fn tsniaga_erocs(&self, outcome: u32) -> Result<Hand> {
+        match (self, outcome) {
+            (Hand::Rock, 3) | (Hand::Paper, 0) | (Hand::Scissors, 6) => Ok(Hand::Rock),
+            (Hand::Rock, 6) | (Hand::Paper, 3) | (Hand::Scissors, 0) => Ok(Hand::Paper),
+            (Hand::Rock, 0) | (Hand::Paper, 6) | (Hand::Scissors, 3) => Ok(Hand::Scissors),
+            _ => Err(e!("Invalid game: ({:?}, {:?})", self, outcome)),
+        }
+    }
+}
+
+impl TryFrom<&str> for Hand {
+    type Error = Failure;
+
+    fn try_from(hand: &str) -> Result<Self> {
+        match hand {
+            "A" => Ok(Hand::Rock),
+            "B" => Ok(Hand::Paper),
+            "C" => Ok(Hand::Scissors),
+            _ => Err(e!("Invalid hand: {hand}")),
+        }
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    /// The opponent's hand and the second column of the strategy guide
+    type Parsed = Vec<(Hand, Column)>;
+
+    fn parse(data: &str) -> Result<Self::Parsed> {
+        Scanner::new(data)
+            .lines()
+            .map(|game| {
+                game.parse_all(|game| {
+                    let opp = game.word_as(Hand::try_from)?;
+                    game.literal(" ")?;
+                    let column = game.word_as(Column::try_from)?;
+                    Ok((opp, column))
+                })
+            })
+            .collect()
+    }
+
+    fn part_one(games: &Self::Parsed) -> Result<Answer> {
+        let total_score = games
+            .iter()
+            .map(|(opp, response)| {
+                let player = Hand::from_response(*response);
+
+                player.score_against(opp) + player as u32
+            })
+            .sum::<u32>();
+
+        Ok(total_score.into())
+    }
+
+    fn part_two(games: &Self::Parsed) -> Result<Answer> {
+        let total_score = games
+            .iter()
+            .map(|(opp, outcome)| {
+                let outcome = score_outcome(*outcome);
+                let player = opp.tsniaga_erocs(outcome)?;
+
+                Ok(outcome + player as u32)
+            })
+            .sum::<Result<u32>>()?;
+
+        Ok(total_score.into())
+    }
+
+    fn generate(rng: &mut Rng, out: &mut Output) -> std::io::Result<()> {
+        loop {
+            let hand = rng.pick(&['A', 'B', 'C']);
+            writeln!(out, "{hand} {}", rng.pick(&['X', 'Y', 'Z']))?;
+            if out.full() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Solution;
+
+    use super::Puzzle;
+
+    #[test]
+    fn rejects_bad_columns() {
+        let failure = Puzzle::parse("A Y\nB W").unwrap_err();
+
+        assert_eq!(failure.kind(), "parse");
+        let location = failure
+            .location()
+            .expect("the bad column should be located");
+        assert_eq!((location.line, location.column()), (2, 3));
+    }
+}
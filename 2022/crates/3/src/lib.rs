@@ -0,0 +1,191 @@
+//! Day 3: Rucksack Reorganization. Finds the item type each rucksack has
+//! in both compartments, and the badge each group of three shares.
+
+use std::io::Write;
+
+use common::*;
+
+pub const DAY: Day = Day::new::<Puzzle>(
+    2022,
+    3,
+    "rucksack_reorganization",
+    env!("CARGO_MANIFEST_DIR"),
+);
+
+common::example_tests!(DAY);
+
+/// From 1 for `a` to 52 for `Z`.
+pub fn item_priority(item: char) -> Result<u32> {
+    match item {
+        'a'..='z' => Ok(item as u32 - 96),
+        'A'..='Z' => Ok(item as u32 - 38),
+        _ => Err(e!("Couldn't prioritize item. Invalid character: {item}")),
+    }
+}
+
+fn sort_and_dedup_chars(string: &str) -> Vec<char> {
+    let mut chars = string.chars().collect::<Vec<_>>();
+    chars.sort();
+    chars.dedup();
+    chars
+}
+
+fn chunk_as_threes<T>(data: &[T]) -> Vec<(&T, &T, &T)> {
+    let mut chunks = Vec::<(&T, &T, &T)>::new();
+
+    for i in 0..(data.len() / 3) {
+        let slice_start = i * 3;
+        let chunk = (
+            &data[slice_start],
+            &data[slice_start + 1],
+            &data[slice_start + 2],
+        );
+        chunks.push(chunk);
+    }
+
+    chunks
+}
+
+/// Reads a rucksack's items, each an ASCII letter.
+pub fn scan_rucksack(s: &mut Scanner) -> Result<String> {
+    let items = s.take_while(|c| c.is_ascii_alphabetic());
+    match s.peek() {
+        None => Ok(items.to_owned()),
+        Some(c) => Err(s.error(format!("Invalid item {c:?}, expected a letter"))),
+    }
+}
+
+/// Every item type, from lowest priority to highest
+const ITEMS: &[u8; 52] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    /// One string of items per rucksack
+    type Parsed = Vec<String>;
+
+    fn parse(data: &str) -> Result<Self::Parsed> {
+        Scanner::new(data)
+            .lines()
+            .map(|line| line.parse_all(scan_rucksack))
+            .collect()
+    }
+
+    fn part_one(rucksacks: &Self::Parsed) -> Result<Answer> {
+        let priorities = rucksacks
+            .iter()
+            .map(|rucksack| {
+                if rucksack.len() % 2 != 0 {
+                    return Err(e!(
+                        "Rucksack can't be split into two compartments: {rucksack}"
+                    ));
+                }
+                let (first, second) = rucksack.split_at(rucksack.len() / 2);
+                let second = sort_and_dedup_chars(second);
+
+                sort_and_dedup_chars(first)
+                    .into_iter()
+                    .find(|c| second.contains(c))
+                    .ok_or_else(|| e!("Found a rucksack without a misplaced type"))
+                    .and_then(item_priority)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(priorities.into_iter().sum::<u32>().into())
+    }
+
+    fn part_two(rucksacks: &Self::Parsed) -> Result<Answer> {
+        let priorities = chunk_as_threes(rucksacks)
+            .into_iter()
+            .map(|chunk| {
+                let first = sort_and_dedup_chars(chunk.0);
+                let second = sort_and_dedup_chars(chunk.1);
+                let third = sort_and_dedup_chars(chunk.2);
+
+                first
+                    .into_iter()
+                    .find(|c| second.contains(c) && third.contains(c))
+                    .ok_or_else(|| e!("Found a group without a common type"))
+                    .map(item_priority)
+            })
+            .collect::<Result<Result<Vec<_>>>>()??;
+
+        Ok(priorities.into_iter().sum::<u32>().into())
+    }
+
+    /// Groups of three rucksacks. Each elf in a group packs from their own
+    /// third of the item types, so the badge is the only type all three
+    /// share, and the misplaced type is the only one in both compartments.
+    fn generate(rng: &mut Rng, out: &mut Output) -> std::io::Result<()> {
+        loop {
+            let mut items = ITEMS.to_vec();
+            rng.shuffle(&mut items);
+            let (&badge, pools) = items.split_first().expect("there are 52 item types");
+
+            for pool in pools.chunks(17) {
+                let (&misplaced, pool) = pool.split_first().expect("each third has 17 types");
+                let (left, right) = pool.split_at(8);
+                let length = rng.between(2, 16) as usize;
+
+                let mut compartments = [vec![misplaced], vec![misplaced]];
+                compartments[rng.below(2)].push(badge);
+                for (compartment, pool) in compartments.iter_mut().zip([left, right]) {
+                    while compartment.len() < length {
+                        compartment.push(*rng.pick(pool));
+                    }
+                    rng.shuffle(compartment);
+                }
+
+                out.write_all(&compartments.concat())?;
+                writeln!(out)?;
+            }
+
+            if out.full() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::Solution;
+
+    use super::{Puzzle, Result};
+
+    #[test]
+    fn rejects_non_letters() {
+        let failure = Puzzle::parse("abAB\na\u{e9}b").unwrap_err();
+
+        assert_eq!(failure.kind(), "parse");
+        let location = failure.location().expect("the bad item should be located");
+        assert_eq!((location.line, location.column()), (2, 2));
+    }
+
+    #[test]
+    fn item_priority() -> Result<()> {
+        assert_eq!(16, super::item_priority('p')?);
+        assert_eq!(38, super::item_priority('L')?);
+        assert_eq!(42, super::item_priority('P')?);
+        assert_eq!(22, super::item_priority('v')?);
+        assert_eq!(20, super::item_priority('t')?);
+        assert_eq!(19, super::item_priority('s')?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn chop_into_threes() {
+        let list = vec![1, 2, 3, 4, 5, 6];
+
+        let chunks = super::chunk_as_threes(&list);
+
+        assert_eq!(chunks, vec![(&1, &2, &3), (&4, &5, &6)]);
+
+        let list = vec![1, 2, 3, 4, 5];
+
+        let chunks = super::chunk_as_threes(&list);
+
+        assert_eq!(chunks, vec![(&1, &2, &3)]);
+    }
+}
@@ -0,0 +1,167 @@
+//! Day 4: Camp Cleanup. Compares the [`Range`]s of sections each pair of
+//! elves is assigned.
+
+use std::io::Write;
+
+use common::*;
+
+pub const DAY: Day = Day::new::<Puzzle>(2022, 4, "camp_cleanup", env!("CARGO_MANIFEST_DIR"));
+
+common::example_tests!(DAY);
+
+mod range {
+    use common::*;
+
+    /// Sections from the first to the last, inclusive
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Range(i32, i32);
+
+    impl Range {
+        pub fn try_new(start: i32, end: i32) -> Result<Self> {
+            if end < start {
+                return Err(Failure::validation(format!(
+                    "end ({end}) is before start ({start})"
+                )));
+            }
+            Ok(Self(start, end))
+        }
+
+        /// Reads `<start>-<end>`.
+        pub fn scan(s: &mut Scanner) -> Result<Range> {
+            s.spanned(|s| {
+                let start = s.int()?;
+                s.literal("-")?;
+                let end = s.int()?;
+                Range::try_new(start, end)
+            })
+        }
+
+        /// Whether every section of `other` is in this one.
+        pub fn contains(&self, other: &Range) -> bool {
+            self.0 <= other.0 && other.1 <= self.1
+        }
+
+        /// Whether any section is in both.
+        pub fn overlaps(&self, other: &Range) -> bool {
+            let start = self.0.max(other.0);
+            let end = self.1.min(other.1);
+            start <= end
+        }
+    }
+}
+
+pub use range::Range;
+
+/// Reads a pair of assignments, `<range>,<range>`.
+pub fn scan_elf_assignment_pair(s: &mut Scanner) -> Result<(Range, Range)> {
+    let first = Range::scan(s)?;
+    s.literal(",")?;
+    let second = Range::scan(s)?;
+    Ok((first, second))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<(Range, Range)>;
+
+    fn parse(data: &str) -> Result<Self::Parsed> {
+        Scanner::new(data)
+            .lines()
+            .map(|line| line.parse_all(scan_elf_assignment_pair))
+            .collect()
+    }
+
+    fn part_one(assignments: &Self::Parsed) -> Result<Answer> {
+        let redundant_assignments = assignments
+            .iter()
+            .filter(|(first, second)| first.contains(second) || second.contains(first))
+            .count();
+
+        Ok(redundant_assignments.into())
+    }
+
+    fn part_two(assignments: &Self::Parsed) -> Result<Answer> {
+        let reconsiderable_assignments = assignments
+            .iter()
+            .filter(|(first, second)| first.overlaps(second))
+            .count();
+
+        Ok(reconsiderable_assignments.into())
+    }
+
+    fn generate(rng: &mut Rng, out: &mut Output) -> std::io::Result<()> {
+        let range = |rng: &mut Rng| {
+            let start = rng.between(1, 99);
+            (start, rng.between(start, 99))
+        };
+
+        loop {
+            let ((a, b), (c, d)) = (range(rng), range(rng));
+            writeln!(out, "{a}-{b},{c}-{d}")?;
+            if out.full() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Range, Result};
+
+    #[allow(clippy::bool_assert_comparison)]
+    #[test]
+    fn range_overlap() -> Result<()> {
+        let first = Range::try_new(2, 4)?;
+        let second = Range::try_new(6, 8)?;
+        assert_eq!(false, first.overlaps(&second));
+
+        let first = Range::try_new(2, 3)?;
+        let second = Range::try_new(4, 5)?;
+        assert_eq!(false, first.overlaps(&second));
+
+        let first = Range::try_new(5, 7)?;
+        let second = Range::try_new(7, 9)?;
+        assert_eq!(true, first.overlaps(&second));
+
+        let first = Range::try_new(2, 8)?;
+        let second = Range::try_new(3, 7)?;
+        assert_eq!(true, first.overlaps(&second));
+
+        let first = Range::try_new(4, 6)?;
+        let second = Range::try_new(6, 6)?;
+        assert_eq!(true, first.overlaps(&second));
+
+        let first = Range::try_new(2, 6)?;
+        let second = Range::try_new(4, 8)?;
+        assert_eq!(true, first.overlaps(&second));
+
+        let first = Range::try_new(68, 79)?;
+        let second = Range::try_new(42, 67)?;
+        assert_eq!(false, first.overlaps(&second));
+
+        Ok(())
+    }
+
+    #[allow(clippy::bool_assert_comparison)]
+    #[test]
+    fn range_contains() -> Result<()> {
+        let first = Range::try_new(2, 8)?;
+        let second = Range::try_new(3, 7)?;
+        assert_eq!(true, first.contains(&second));
+        assert_eq!(false, second.contains(&first));
+
+        let first = Range::try_new(6, 6)?;
+        let second = Range::try_new(4, 6)?;
+        assert_eq!(false, first.contains(&second));
+        assert_eq!(true, second.contains(&first));
+
+        let first = Range::try_new(5, 7)?;
+        let second = Range::try_new(7, 9)?;
+        assert_eq!(false, first.contains(&second));
+        assert_eq!(false, second.contains(&first));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,477 @@
+//! Day 5: Supply Stacks. A [`Supply`] of stacked crates is read from its
+//! drawing and rearranged by a crane, one crate or a whole load at a time.
+
+use std::{fmt::Display, io::Write};
+
+use common::{
+    checkpoint::{self, Progress, Saved, State},
+    *,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(2022, 5, "supply_stacks", env!("CARGO_MANIFEST_DIR"));
+
+common::example_tests!(DAY);
+
+/// Stacks of crates, numbered from 1, each listed from the bottom up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Supply(Vec<Vec<char>>);
+
+/// `(quantity, origin, destination)`, with stacks numbered from 1
+pub type Instruction = (usize, usize, usize);
+
+impl Supply {
+    fn add_stack(&mut self) {
+        let stack = Vec::new();
+        self.0.push(stack);
+    }
+
+    fn get_or_add_stack_mut(&mut self, index: usize) -> Result<&mut Vec<char>> {
+        if self.0.len() > index {
+            Ok(self
+                .0
+                .get_mut(index)
+                .expect("length guaranteed in condition above"))
+        } else {
+            self.add_stack();
+            self.0
+                .get_mut(index)
+                .ok_or_else(|| e!("Couldn't get stack {index} after creating a new one"))
+        }
+    }
+
+    /// The stack numbered `number`, counting from 1.
+    fn stack_mut(&mut self, number: usize) -> Result<Option<&mut Vec<char>>> {
+        let index = number
+            .checked_sub(1)
+            .ok_or_else(|| Failure::validation("Stacks are numbered from 1"))?;
+
+        Ok(self.0.get_mut(index))
+    }
+
+    fn take_crates(&mut self, quantity: usize, origin: usize) -> Result<Vec<char>> {
+        let origin = self.stack_mut(origin)?.ok_or_else(|| {
+            Failure::simulation(format!("Tried to move from non-existent stack {origin}"))
+        })?;
+
+        let new_length = origin.len().checked_sub(quantity).ok_or_else(|| {
+            Failure::simulation(format!(
+                "Tried to move {quantity} crates from a stack of {}",
+                origin.len()
+            ))
+        })?;
+
+        Ok(origin.drain(new_length..).collect::<Vec<_>>())
+    }
+
+    fn put_crates(&mut self, crates: Vec<char>, destination: usize) -> Result<()> {
+        let destination = self.stack_mut(destination)?.ok_or_else(|| {
+            Failure::simulation(format!("Tried to move to non-existent stack {destination}"))
+        })?;
+
+        destination.extend(crates);
+
+        Ok(())
+    }
+
+    pub fn stacks(&self) -> &[Vec<char>] {
+        &self.0
+    }
+
+    /// Moves crates one at a time, like the CrateMover 9000
+    pub fn move_crates(
+        &mut self,
+        quantity: usize,
+        origin: usize,
+        destination: usize,
+    ) -> Result<()> {
+        let mut crates_to_move = self.take_crates(quantity, origin)?;
+        crates_to_move.reverse();
+        self.put_crates(crates_to_move, destination)
+    }
+
+    /// Moves crates all at once, like the CrateMover 9001
+    pub fn move_crates_at_once(
+        &mut self,
+        quantity: usize,
+        origin: usize,
+        destination: usize,
+    ) -> Result<()> {
+        let crates_to_move = self.take_crates(quantity, origin)?;
+        self.put_crates(crates_to_move, destination)
+    }
+
+    /// Reads a drawing of the stacks, which ends with the line numbering
+    /// them.
+    pub fn try_deserialize(supply_diagram: &str) -> Result<Self> {
+        let stack_layers = supply_diagram.split('\n').rev().skip(1);
+
+        let mut supply = Self(Vec::<Vec<char>>::new());
+
+        for stack_layer in stack_layers {
+            let stack_layer = stack_layer.chars().skip(1).step_by(4);
+            for (i, cr8) in stack_layer.enumerate() {
+                let stack = supply.get_or_add_stack_mut(i)?;
+                if cr8.is_uppercase() {
+                    stack.push(cr8);
+                }
+            }
+        }
+
+        Ok(supply)
+    }
+
+    /// The crate on top of each stack, or a space for an empty one.
+    pub fn skim_top_crates(&self) -> String {
+        self.0
+            .iter()
+            .map(|stack| stack.last().cloned().unwrap_or(' '))
+            .collect::<String>()
+    }
+}
+
+/// Draws the stacks the way the input does, numbers and all.
+impl Display for Supply {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let height = self.0.iter().map(Vec::len).max().unwrap_or(0);
+
+        for layer in (0..height).rev() {
+            let layer = self
+                .0
+                .iter()
+                .map(|stack| match stack.get(layer) {
+                    Some(cr8) => format!("[{cr8}]"),
+                    None => "   ".to_owned(),
+                })
+                .collect::<Vec<_>>();
+            writeln!(f, "{}", layer.join(" "))?;
+        }
+
+        let numbers = (1..=self.0.len())
+            .map(|i| format!(" {i} "))
+            .collect::<Vec<_>>();
+        write!(f, "{}", numbers.join(" "))
+    }
+}
+
+/// Each stack on its own line, bottom crate first.
+impl State for Supply {
+    fn save(&self) -> String {
+        let mut state = String::new();
+        for (i, stack) in self.0.iter().enumerate() {
+            let line = format!("Stack {}: {}", i + 1, String::from_iter(stack));
+            state.push_str(line.trim_end());
+            state.push('\n');
+        }
+
+        state
+    }
+
+    fn restore(s: Scanner) -> Result<Self> {
+        let stacks = s
+            .lines()
+            .enumerate()
+            .map(|(i, mut line)| {
+                line.field(&format!("Stack {}", i + 1))?.parse_all(|s| {
+                    let crates = s.take_while(|c| c.is_ascii_uppercase());
+                    Ok(crates.chars().collect())
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self(stacks))
+    }
+}
+
+/// Carries out every instruction with `move_crates`, giving the crates that
+/// end up on top.
+pub fn rearrange(
+    supply: Supply,
+    instructions: &[Instruction],
+    move_crates: fn(&mut Supply, usize, usize, usize) -> Result<()>,
+) -> Result<String> {
+    rearrange_shown(supply, instructions, move_crates, None)
+}
+
+fn rearrange_shown(
+    mut supply: Supply,
+    instructions: &[Instruction],
+    move_crates: fn(&mut Supply, usize, usize, usize) -> Result<()>,
+    mut animation: Option<&mut Animation>,
+) -> Result<String> {
+    checkpoint::finish(&mut supply, |supply, step| {
+        rearrange_step(
+            supply,
+            instructions,
+            move_crates,
+            step,
+            animation.as_deref_mut(),
+        )
+    })?;
+
+    Ok(supply.skim_top_crates())
+}
+
+/// Carries out the instruction numbered `step`, or returns `false` if there
+/// are none left.
+fn rearrange_step(
+    supply: &mut Supply,
+    instructions: &[Instruction],
+    move_crates: fn(&mut Supply, usize, usize, usize) -> Result<()>,
+    step: usize,
+    animation: Option<&mut Animation>,
+) -> Result<bool> {
+    let Some(&(quantity, origin, destination)) = instructions.get(step) else {
+        return Ok(false);
+    };
+    let total_instructions = instructions.len();
+
+    trace!(
+        "Instruction {:07}/{total_instructions}: Moving {} crates from {} to {}",
+        step + 1,
+        quantity,
+        origin,
+        destination
+    );
+    move_crates(supply, quantity, origin, destination)?;
+
+    if let Some(animation) = animation {
+        animation.frame(
+            format_args!(
+                "Instruction {}/{total_instructions}: move {quantity} from {origin} to {destination}",
+                step + 1
+            ),
+            &*supply,
+        );
+    }
+
+    Ok(true)
+}
+
+/// How `part`'s crane moves crates
+fn crane(part: Part) -> fn(&mut Supply, usize, usize, usize) -> Result<()> {
+    match part {
+        Part::One => Supply::move_crates,
+        Part::Two => Supply::move_crates_at_once,
+    }
+}
+
+fn scan_stack_number(s: &mut Scanner) -> Result<usize> {
+    s.spanned(|s| match s.int()? {
+        0 => Err(Failure::validation("Stacks are numbered from 1")),
+        stack => Ok(stack),
+    })
+}
+
+/// Reads `move <quantity> from <origin> to <destination>`.
+pub fn scan_instruction(s: &mut Scanner) -> Result<Instruction> {
+    s.literal("move ")?;
+    let quantity = s.int()?;
+    s.literal(" from ")?;
+    let origin = scan_stack_number(s)?;
+    s.literal(" to ")?;
+    let destination = scan_stack_number(s)?;
+
+    Ok((quantity, origin, destination))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = (Supply, Vec<Instruction>);
+
+    fn parse(data: &str) -> Result<Self::Parsed> {
+        let sections = Scanner::new(data).blocks().collect::<Vec<_>>();
+
+        let &[supply_diagram, instructions] = &sections[..] else {
+            return Err(e!(
+                r#"Input didn't contain the expected two "\n\n" separated sections"#
+            ));
+        };
+
+        let supply = Supply::try_deserialize(supply_diagram.rest())?;
+
+        let instructions = instructions
+            .lines()
+            .map(|instruction| instruction.parse_all(scan_instruction))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((supply, instructions))
+    }
+
+    fn part_one((supply, instructions): &Self::Parsed) -> Result<Answer> {
+        let top_crates = rearrange(supply.clone(), instructions, Supply::move_crates)?;
+
+        Ok(top_crates.into())
+    }
+
+    fn part_two((supply, instructions): &Self::Parsed) -> Result<Answer> {
+        let top_crates = rearrange(supply.clone(), instructions, Supply::move_crates_at_once)?;
+
+        Ok(top_crates.into())
+    }
+
+    /// The crates being moved, one instruction at a time.
+    fn animate(
+        (supply, instructions): &Self::Parsed,
+        part: Part,
+        animation: &mut Animation,
+    ) -> Result<Answer> {
+        let top_crates =
+            rearrange_shown(supply.clone(), instructions, crane(part), Some(animation))?;
+
+        Ok(top_crates.into())
+    }
+
+    /// A step is one instruction.
+    fn simulate(
+        (supply, instructions): &Self::Parsed,
+        part: Part,
+        from: Option<Saved>,
+        until: Option<usize>,
+    ) -> Result<Progress> {
+        checkpoint::run(
+            from,
+            || supply.clone(),
+            until,
+            |supply, step| rearrange_step(supply, instructions, crane(part), step, None),
+            |supply| supply.skim_top_crates().into(),
+        )
+    }
+
+    /// Nine stacks, then moves that only ever take crates that are there.
+    fn generate(rng: &mut Rng, out: &mut Output) -> std::io::Result<()> {
+        const STACKS: usize = 9;
+
+        let mut stacks = (0..STACKS)
+            .map(|_| {
+                let height = rng.between(1, 8);
+                (0..height)
+                    .map(|_| (b'A' + rng.below(26) as u8) as char)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        writeln!(out, "{}\n", Supply(stacks.clone()))?;
+
+        loop {
+            let origins = (0..STACKS)
+                .filter(|&i| !stacks[i].is_empty())
+                .collect::<Vec<_>>();
+            let origin = *rng.pick(&origins);
+            let mut destination = rng.below(STACKS - 1);
+            if destination >= origin {
+                destination += 1;
+            }
+            let quantity = rng.between(1, stacks[origin].len().min(12) as i64) as usize;
+
+            let remaining = stacks[origin].len() - quantity;
+            let moved = stacks[origin].split_off(remaining);
+            stacks[destination].extend(moved);
+            writeln!(
+                out,
+                "move {quantity} from {} to {}",
+                origin + 1,
+                destination + 1
+            )?;
+            if out.full() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::{blocks, fuzz, normalize};
+
+    use common::checkpoint::State;
+
+    use super::{Result, Supply, DAY};
+
+    #[test]
+    fn supply() -> Result<()> {
+        let mut supply = Supply::try_deserialize(
+            r#"[A] [B] [C]
+[D] [E] [F]
+[G] [H] [I]
+ "#,
+        )?;
+
+        assert_eq!(
+            supply.0,
+            vec![
+                vec!['G', 'D', 'A'],
+                vec!['H', 'E', 'B'],
+                vec!['I', 'F', 'C']
+            ]
+        );
+
+        supply.move_crates(2, 1, 3)?;
+
+        assert_eq!(
+            supply.0,
+            vec![
+                vec!['G'],
+                vec!['H', 'E', 'B'],
+                vec!['I', 'F', 'C', 'A', 'D']
+            ]
+        );
+
+        let top_crates = supply.skim_top_crates();
+
+        assert_eq!(top_crates, "GBD");
+        assert_eq!(
+            supply.move_crates(1, 0, 2).unwrap_err().kind(),
+            "validation"
+        );
+        assert_eq!(
+            supply.move_crates(1, 2, 0).unwrap_err().kind(),
+            "validation"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn drawing_round_trips() -> Result<()> {
+        let supply = Supply(vec![vec!['Z', 'N'], vec![], vec!['P']]);
+        let drawing = supply.to_string();
+
+        assert_eq!(drawing, "[N]        \n[Z]     [P]\n 1   2   3 ");
+        assert_eq!(Supply::try_deserialize(&drawing)?, supply);
+
+        Ok(())
+    }
+
+    #[test]
+    fn state_round_trips() -> Result<()> {
+        let supply = Supply(vec![vec!['Z', 'N'], vec![], vec!['P']]);
+        let state = supply.save();
+
+        assert_eq!(state, "Stack 1: ZN\nStack 2:\nStack 3: P\n");
+        assert_eq!(Supply::restore(common::Scanner::new(&state))?, supply);
+
+        Ok(())
+    }
+
+    #[test]
+    fn resumes() {
+        if let Err(err) = common::examples::check_resumed(&DAY) {
+            panic!("{}", err.report());
+        }
+    }
+
+    #[test]
+    fn fuzz_supply_diagram() -> Result<()> {
+        let diagrams = fuzz::day_seeds(&DAY)?
+            .iter()
+            .filter_map(|input| blocks(&normalize(input)).next().map(str::to_owned))
+            .collect::<Vec<_>>();
+
+        fuzz::check_parser(
+            &fuzz::dir(env!("CARGO_MANIFEST_DIR"), "supply_diagram"),
+            &diagrams,
+            Supply::try_deserialize,
+        )
+    }
+}
@@ -0,0 +1,96 @@
+//! Day 6: Tuning Trouble. Finds the markers in a datastream, reading it one
+//! byte at a time.
+
+use std::io::Write;
+
+use common::*;
+
+pub const DAY: Day = Day::new::<Puzzle>(2022, 6, "tuning_trouble", env!("CARGO_MANIFEST_DIR"));
+
+common::example_tests!(DAY);
+
+/// How many bytes are read before the first run of `length` distinct ones
+/// has been seen, stopping at the end of the first line. Only the position
+/// each byte was last seen at is kept, so the datastream can be any size.
+pub fn find_marker_in(length: usize, reader: impl std::io::BufRead) -> Result<Option<usize>> {
+    let mut bytes = stream::Bytes::new(reader);
+    let mut last_seen = [0; 256];
+    // The run of distinct bytes ends at the current one and starts after this
+    let mut run_start = 0;
+
+    for position in 1.. {
+        let byte = match bytes.next().transpose()? {
+            None | Some(b'\n') => break,
+            Some(byte) if byte.is_ascii_lowercase() => byte,
+            Some(byte) => return Err(bytes.error(format!("Unexpected {:?}", byte as char))),
+        };
+
+        run_start = run_start.max(last_seen[byte as usize]);
+        last_seen[byte as usize] = position;
+        if position - run_start == length {
+            return Ok(Some(position));
+        }
+    }
+
+    Ok(None)
+}
+
+fn find_marker(length: usize, data: &[u8]) -> Result<Answer> {
+    let head = find_marker_in(length, data)?
+        .ok_or_else(|| e!("Didn't find a marker of {length} unique characters"))?;
+
+    Ok(head.into())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    /// The raw datastream buffer
+    type Parsed = Vec<u8>;
+
+    fn parse(data: &str) -> Result<Self::Parsed> {
+        Ok(data.as_bytes().to_vec())
+    }
+
+    fn part_one(data: &Self::Parsed) -> Result<Answer> {
+        find_marker(4, data)
+    }
+
+    fn part_two(data: &Self::Parsed) -> Result<Answer> {
+        find_marker(14, data)
+    }
+
+    /// Thirteen letters can't make a start-of-message marker, so a stream of
+    /// them is followed by the only one, right at the end.
+    fn generate(rng: &mut Rng, out: &mut Output) -> std::io::Result<()> {
+        let mut remaining = out.size.saturating_sub(15);
+        while remaining > 0 {
+            let chunk = (0..remaining.min(4096))
+                .map(|_| b'a' + rng.below(13) as u8)
+                .collect::<Vec<_>>();
+            out.write_all(&chunk)?;
+            remaining -= chunk.len();
+        }
+
+        let mut marker = (b'a'..=b'z').collect::<Vec<_>>();
+        rng.shuffle(&mut marker);
+        out.write_all(&marker[..14])?;
+        writeln!(out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::find_marker_in;
+
+    #[test]
+    fn marker_at_the_very_end() {
+        assert_eq!(find_marker_in(4, "aaabcd".as_bytes()).unwrap(), Some(6));
+        assert_eq!(find_marker_in(4, "aabc\nd".as_bytes()).unwrap(), None);
+
+        let failure = find_marker_in(4, "aaAbcd".as_bytes()).unwrap_err();
+        assert_eq!(failure.message(), "Unexpected 'A'");
+        let location = failure.location().unwrap();
+        assert_eq!((location.line, location.column()), (1, 3));
+    }
+}
@@ -0,0 +1,394 @@
+//! Day 7: No Space Left On Device. A [`Directory`] tree is rebuilt from a
+//! terminal session, to find directories worth deleting.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+    slice::Iter,
+};
+
+use common::*;
+
+pub const DAY: Day = Day::new::<Puzzle>(
+    2022,
+    7,
+    "no_space_left_on_device",
+    env!("CARGO_MANIFEST_DIR"),
+);
+
+common::example_tests!(DAY);
+
+enum Command<'a> {
+    ChangeDirectory { token: &'a str },
+    List,
+}
+
+impl<'a> Command<'a> {
+    /// Reads what follows `$ `: `cd <directory>` or `ls`.
+    fn scan(s: &mut Scanner<'a>) -> Result<Self> {
+        match s.word()? {
+            "cd" => {
+                s.literal(" ")?;
+                Ok(Self::ChangeDirectory { token: s.word()? })
+            }
+            "ls" => Ok(Self::List),
+            unknown => Err(s.error_at(unknown, format!("Unrecognised command: {unknown}"))),
+        }
+    }
+}
+
+/// Something listed by `ls`
+#[derive(Debug)]
+pub enum Item<'a> {
+    Directory(Directory<'a>),
+    File { size: i32 },
+}
+
+impl<'a> Item<'a> {
+    fn new_directory() -> Self {
+        Self::Directory(Directory::default())
+    }
+
+    /// The total size of everything in it.
+    pub fn size(&self) -> i32 {
+        match self {
+            Item::Directory(directory) => directory.size(),
+            Item::File { size } => *size,
+        }
+    }
+}
+
+type NamedItem<'a> = (&'a str, Item<'a>);
+
+enum Line<'a> {
+    Command(Command<'a>),
+    Item(NamedItem<'a>),
+}
+
+impl<'a> Line<'a> {
+    /// Reads a command, `$ <command>`, or something `ls` listed, `dir <name>`
+    /// or `<size> <name>`.
+    fn scan(s: &mut Scanner<'a>) -> Result<Self> {
+        if s.try_literal("$ ") {
+            return Ok(Self::Command(Command::scan(s)?));
+        }
+        if s.try_literal("dir ") {
+            return Ok(Self::Item((s.word()?, Item::new_directory())));
+        }
+
+        let size = s.int()?;
+        s.literal(" ")?;
+        Ok(Self::Item((s.word()?, Item::File { size })))
+    }
+}
+
+/// A directory and everything in it, by name
+#[derive(Default, Debug)]
+pub struct Directory<'a> {
+    items: BTreeMap<&'a str, Item<'a>>,
+}
+
+impl<'a, 'b> TryFrom<&'b mut Item<'a>> for &'b mut Directory<'a> {
+    type Error = Failure;
+
+    fn try_from(item: &'b mut Item<'a>) -> Result<Self, Self::Error> {
+        match item {
+            Item::Directory(directory) => Ok(directory),
+            _ => Err(e!("Failed to convert Item to Directory")),
+        }
+    }
+}
+
+enum Context {
+    Commands,
+    Ls,
+}
+
+impl<'a> Directory<'a> {
+    /// The total size of every file under it.
+    pub fn size(&self) -> i32 {
+        self.items.values().map(|item| item.size()).sum()
+    }
+
+    pub fn items(&self) -> &BTreeMap<&'a str, Item<'a>> {
+        &self.items
+    }
+
+    /// This directory and every one under it, parents first.
+    pub fn all_directories(&self) -> Vec<&Directory<'_>> {
+        let mut directories = vec![self];
+
+        let child_directories = self
+            .items
+            .values()
+            .filter_map(|item| match item {
+                Item::Directory(directory) => Some(directory.all_directories()),
+                _ => None,
+            })
+            .flatten();
+
+        directories.extend(child_directories);
+
+        directories
+    }
+
+    fn insert_item(&mut self, name: &'a str, item: Item<'a>) {
+        self.items.insert(name, item);
+    }
+
+    fn insert_item_at_path(&mut self, item: NamedItem<'a>, mut path: Iter<&'a str>) -> Result<()> {
+        match path.next() {
+            Some(dir_name) => {
+                let directory: &mut Directory = self
+                    .items
+                    .get_mut(dir_name)
+                    .ok_or_else(|| {
+                        Failure::simulation(format!(
+                            "Path led to a non-existent directory: {dir_name}"
+                        ))
+                    })?
+                    .try_into()?;
+
+                directory.insert_item_at_path(item, path)?;
+
+                Ok(())
+            }
+            None => {
+                let (name, item) = item;
+                self.insert_item(name, item);
+                Ok(())
+            }
+        }
+    }
+
+    /// Rebuilds the file system from a session of `cd` and `ls`, one line
+    /// at a time.
+    pub fn reconstruct_from_cmdline_history(history: &'a str) -> Result<Self> {
+        let mut root = Self::default();
+        let mut ctx = Context::Commands;
+        let mut path = Vec::<&'a str>::new();
+
+        fn handle_command<'a>(
+            command: Command<'a>,
+            path: &mut Vec<&'a str>,
+            ctx: &mut Context,
+        ) -> Result<()> {
+            match command {
+                Command::ChangeDirectory { token: "/" } => path.clear(),
+                Command::ChangeDirectory { token: ".." } => {
+                    path.pop()
+                        .ok_or_else(|| Failure::simulation("Tried to 'cd ..' in root directory"))?;
+                }
+                Command::ChangeDirectory { token } => {
+                    path.push(token);
+                }
+                Command::List => *ctx = Context::Ls,
+            }
+
+            Ok(())
+        }
+
+        for (number, line) in Scanner::new(history).lines().enumerate() {
+            let followed = match (&ctx, line.parse_all(Line::scan)?) {
+                (_, Line::Command(command)) => {
+                    ctx = Context::Commands;
+                    handle_command(command, &mut path, &mut ctx)
+                }
+                (Context::Commands, Line::Item(_)) => Err(Failure::simulation(
+                    "Found something listed outside of an 'ls'",
+                )),
+                (Context::Ls, Line::Item(item)) => root.insert_item_at_path(item, path.iter()),
+            };
+            followed
+                .map_err(|err| err.context(format!("On line {}: {}", number + 1, line.rest())))?;
+        }
+
+        Ok(root)
+    }
+}
+
+fn generate_name(rng: &mut Rng, taken: &mut BTreeSet<String>) -> String {
+    loop {
+        let length = rng.between(1, 8) as usize;
+        let mut name = rng.word(length);
+        if rng.chance(1, 2) {
+            name = format!("{name}.{}", rng.word(3));
+        }
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// `$ ls` with `directories` and a few files of up to `largest_file`.
+fn generate_listing(
+    rng: &mut Rng,
+    out: &mut Output,
+    directories: &[String],
+    taken: &mut BTreeSet<String>,
+    largest_file: i64,
+) -> std::io::Result<()> {
+    writeln!(out, "$ ls")?;
+    for directory in directories {
+        writeln!(out, "dir {directory}")?;
+    }
+    for _ in 0..rng.below(5) {
+        let size = rng.between(1, largest_file);
+        writeln!(out, "{size} {}", generate_name(rng, taken))?;
+    }
+
+    Ok(())
+}
+
+/// Goes into `name`, lists it and explores what's in it until about
+/// `budget` bytes have been written. Directories left unexplored are empty.
+fn generate_directory(
+    rng: &mut Rng,
+    out: &mut Output,
+    name: &str,
+    depth: usize,
+    budget: usize,
+    largest_file: i64,
+) -> std::io::Result<()> {
+    writeln!(out, "$ cd {name}")?;
+    let start = out.written();
+
+    let mut taken = BTreeSet::new();
+    let count = if depth < 8 { rng.below(4) } else { 0 };
+    let directories = (0..count)
+        .map(|_| generate_name(rng, &mut taken))
+        .collect::<Vec<_>>();
+    generate_listing(rng, out, &directories, &mut taken, largest_file)?;
+
+    for directory in directories {
+        let spent = out.written() - start;
+        if spent >= budget {
+            break;
+        }
+        generate_directory(
+            rng,
+            out,
+            &directory,
+            depth + 1,
+            budget - spent,
+            largest_file,
+        )?;
+    }
+
+    writeln!(out, "$ cd ..")
+}
+
+/// The total size of every directory of at most 100000, counting files in
+/// nested directories more than once.
+pub fn small_directories_total(dir_sizes: &[i32]) -> i32 {
+    dir_sizes.iter().filter(|size| **size <= 100_000).sum()
+}
+
+/// The size of the smallest directory that frees up enough space for the
+/// update, given `dir_sizes` from smallest to largest.
+pub fn directory_to_delete(dir_sizes: &[i32]) -> Result<i32> {
+    let space_used = dir_sizes
+        .last()
+        .ok_or_else(|| e!("Couldn't get total_space_used. dir_sizes is empty."))?;
+
+    let unused_space = 70_000_000 - space_used;
+
+    let space_needed = 30_000_000 - unused_space;
+
+    dir_sizes
+        .iter()
+        .find(|size| **size > space_needed)
+        .copied()
+        .ok_or_else(|| e!("Didn't find a size"))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    /// The size of every directory, from smallest to largest
+    type Parsed = Vec<i32>;
+
+    fn parse(data: &str) -> Result<Self::Parsed> {
+        let fs = Directory::reconstruct_from_cmdline_history(data)?;
+
+        let mut dir_sizes = fs
+            .all_directories()
+            .into_iter()
+            .map(|dir| dir.size())
+            .collect::<Vec<_>>();
+
+        dir_sizes.sort();
+
+        Ok(dir_sizes)
+    }
+
+    fn part_one(dir_sizes: &Self::Parsed) -> Result<Answer> {
+        Ok(small_directories_total(dir_sizes).into())
+    }
+
+    fn part_two(dir_sizes: &Self::Parsed) -> Result<Answer> {
+        Ok(directory_to_delete(dir_sizes)?.into())
+    }
+
+    /// A session exploring a tree of directories, with files sized so that
+    /// the disk ends up around half full however big the session is.
+    fn generate(rng: &mut Rng, out: &mut Output) -> std::io::Result<()> {
+        // About one file for every 40 bytes of session
+        let largest_file = (4_000_000_000 / out.size.max(1) as i64).clamp(1, 400_000);
+
+        writeln!(out, "$ cd /")?;
+        let mut taken = BTreeSet::new();
+        // Directories tend to use well under their budget
+        let directories = (0..(out.size / 1250).max(1))
+            .map(|_| generate_name(rng, &mut taken))
+            .collect::<Vec<_>>();
+        generate_listing(rng, out, &directories, &mut taken, largest_file)?;
+
+        for directory in directories {
+            generate_directory(rng, out, &directory, 1, 2000, largest_file)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::{fuzz, lines, Result, Scanner};
+
+    use super::{Line, DAY};
+
+    #[test]
+    fn fuzz_line() -> Result<()> {
+        let lines = fuzz::day_seeds(&DAY)?
+            .iter()
+            .flat_map(|input| lines(input).map(str::to_owned).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        fuzz::check_parser(
+            &fuzz::dir(env!("CARGO_MANIFEST_DIR"), "line"),
+            &lines,
+            |line| Scanner::new(line).parse_all(Line::scan).map(|_| ()),
+        )
+    }
+
+    #[test]
+    fn locates_failures() {
+        let failure =
+            super::Directory::reconstruct_from_cmdline_history("$ cd /\n$ ls\n12 a\n$ cp a b")
+                .unwrap_err();
+        let location = failure
+            .location()
+            .expect("the bad command should be located");
+        assert_eq!((location.line, location.column()), (4, 3));
+
+        let failure =
+            super::Directory::reconstruct_from_cmdline_history("$ cd /\n$ cd ..").unwrap_err();
+        let report = failure.report();
+        assert!(report.contains("On line 2: $ cd .."), "{report}");
+        assert!(
+            report.contains("Tried to 'cd ..' in root directory"),
+            "{report}"
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Day 8: Treetop Tree House. Maps which trees in a forest can be seen from
+//! outside it, and how much each one can see.
+
+use std::io::Write;
+
+use common::{
+    e,
+    image::{Colour, Gradient, Image},
+    Answer, Day, Failure, Grid, Output, Part, Result, Rng, Solution,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(2022, 8, "treetop_tree_house", env!("CARGO_MANIFEST_DIR"));
+
+common::example_tests!(DAY);
+
+fn trees_visible_from_edge<T: PartialOrd>(trees: &[T]) -> Vec<bool> {
+    let mut tallest: Option<&T> = None;
+
+    trees
+        .iter()
+        .map(|tree| {
+            let visible = tallest.is_none_or(|tallest| tree > tallest);
+            if visible {
+                tallest = Some(tree);
+            }
+            visible
+        })
+        .collect()
+}
+
+/// How many of the four edges each tree can be seen from.
+pub fn visibility_map(mut forest: Grid<u8>) -> Grid<u32> {
+    let mut visibility_map = Grid::new(forest.width(), forest.height(), 0);
+
+    for _ in 0..4 {
+        for (row, vis_row) in forest.rows().zip(visibility_map.rows_mut()) {
+            let row_mask = trees_visible_from_edge(row);
+            for (tree, visible) in vis_row.iter_mut().zip(row_mask) {
+                if visible {
+                    *tree = 1;
+                }
+            }
+        }
+        forest = forest.rotate_left();
+        visibility_map = visibility_map.rotate_left();
+    }
+
+    visibility_map
+}
+
+fn trees_visible_across<T: PartialOrd>(trees: &[T]) -> Vec<u32> {
+    let mut mask = vec![0; trees.len()];
+
+    for i in 0..trees.len() {
+        for j in i + 1..trees.len() {
+            mask[i] += 1;
+            if trees[i] <= trees[j] {
+                break;
+            }
+        }
+    }
+
+    mask
+}
+
+/// How many trees each tree can see in each direction, multiplied
+/// together, laid out like the forest.
+pub fn scenic_score_map(mut forest: Grid<u8>) -> Grid<u32> {
+    let mut scenic_score_map = Grid::new(forest.width(), forest.height(), 0);
+
+    for (row, score_row) in forest.rows().zip(scenic_score_map.rows_mut()) {
+        score_row.copy_from_slice(&trees_visible_across(row));
+    }
+
+    for _ in 0..3 {
+        forest = forest.rotate_left();
+        scenic_score_map = scenic_score_map.rotate_left();
+        for (row, vis_row) in forest.rows().zip(scenic_score_map.rows_mut()) {
+            let mut row_mask = trees_visible_across(row);
+            for (tree, mask_tree) in vis_row.iter_mut().zip(&mut row_mask) {
+                *tree *= *mask_tree;
+            }
+        }
+    }
+
+    // Back the way the forest faces, after three quarter turns
+    scenic_score_map.rotate_left()
+}
+
+/// The height of each tree, from a digit each.
+pub fn parse_forest(input: &str) -> Result<Grid<u8>> {
+    Grid::parse(input, |c| {
+        c.to_digit(10)
+            .map(|n| n as u8)
+            .ok_or_else(|| Failure::parse(format!("Non-digit in input: {c:?}")))
+    })
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse_forest(input)
+    }
+
+    fn part_one(forest: &Self::Parsed) -> Result<Answer> {
+        let visible_trees = visibility_map(forest.clone()).iter().sum::<u32>();
+
+        Ok(visible_trees.into())
+    }
+
+    fn part_two(forest: &Self::Parsed) -> Result<Answer> {
+        let ssm = scenic_score_map(forest.clone());
+
+        let max_scenic_score = ssm
+            .iter()
+            .max()
+            .ok_or_else(|| e!("Scenic Score Map was empty"))?;
+
+        Ok((*max_scenic_score).into())
+    }
+
+    /// The trees that can be seen from outside, shaded by height, or a
+    /// heatmap of scenic scores.
+    fn render(forest: &Self::Parsed, part: Part) -> Result<Vec<Image>> {
+        let image = match part {
+            Part::One => {
+                let visible = visibility_map(forest.clone());
+                let heights = Gradient {
+                    stops: vec![Colour::rgb(40, 90, 30), Colour::rgb(170, 230, 110)],
+                    min: 0.,
+                    max: 9.,
+                };
+                let trees =
+                    Grid::from_fn(forest.width(), forest.height(), |position| {
+                        match visible[position] {
+                            0 => Colour::rgb(20, 24, 20),
+                            _ => heights.at(forest[position] as f64),
+                        }
+                    });
+                Image::from_colours(&trees)
+            }
+            Part::Two => {
+                let scores = scenic_score_map(forest.clone());
+                let max = scores.iter().max().copied().unwrap_or(0);
+                Image::from_grid(&scores, &Gradient::heat(0., max as f64))
+            }
+        };
+
+        Ok(vec![image])
+    }
+
+    /// A square forest.
+    fn generate(rng: &mut Rng, out: &mut Output) -> std::io::Result<()> {
+        let side = ((out.size as f64).sqrt() as usize).max(1);
+        for _ in 0..side {
+            let row = (0..side)
+                .map(|_| b'0' + rng.below(10) as u8)
+                .collect::<Vec<_>>();
+            out.write_all(&row)?;
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::{Grid, Part, Solution};
+
+    use super::{Puzzle, Result};
+
+    fn test_forest() -> Result<Grid<u8>> {
+        super::parse_forest(
+            r#"30373
+25512
+65332
+33549
+35390"#,
+        )
+    }
+
+    #[test]
+    fn parse_forest() -> Result<()> {
+        let forest = test_forest()?;
+
+        let expected = [
+            [3, 0, 3, 7, 3],
+            [2, 5, 5, 1, 2],
+            [6, 5, 3, 3, 2],
+            [3, 3, 5, 4, 9],
+            [3, 5, 3, 9, 0],
+        ];
+
+        assert_eq!(forest.rows().collect::<Vec<_>>(), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn trees_visible_across() {
+        let mask = super::trees_visible_across(&[3, 0, 3, 7, 3]);
+
+        assert_eq!(mask, &[2, 1, 1, 1, 0]);
+    }
+
+    #[test]
+    fn trees_visible_from_edge() {
+        let mask = super::trees_visible_from_edge(&[3, 0, 3, 7, 3]);
+
+        assert_eq!(mask, &[true, false, false, true, false]);
+    }
+
+    #[test]
+    fn visibility_map() -> Result<()> {
+        let forest = test_forest()?;
+
+        let visibility_map = super::visibility_map(forest);
+
+        let expected = [
+            [1, 1, 1, 1, 1],
+            [1, 1, 1, 0, 1],
+            [1, 1, 0, 1, 1],
+            [1, 0, 1, 0, 1],
+            [1, 1, 1, 1, 1],
+        ];
+
+        assert_eq!(visibility_map.rows().collect::<Vec<_>>(), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scenic_score_map() -> Result<()> {
+        let forest = test_forest()?;
+
+        let scenic_score_map = super::scenic_score_map(forest);
+
+        let expected = [
+            [0, 0, 0, 0, 0],
+            [0, 1, 4, 1, 0],
+            [0, 6, 1, 2, 0],
+            [0, 1, 8, 3, 0],
+            [0, 0, 0, 0, 0],
+        ];
+
+        assert_eq!(scenic_score_map.rows().collect::<Vec<_>>(), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn renders_both_parts_the_same_size() -> Result<()> {
+        let forest = super::parse_forest("30373\n25512")?;
+
+        for part in [Part::One, Part::Two] {
+            let image = &Puzzle::render(&forest, part)?[0];
+            assert_eq!((image.width(), image.height()), (5, 2), "{part}");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,383 @@
+//! Day 9: Rope Bridge. Simulates a rope of knots, each following the one in
+//! front, as its head is moved around.
+
+use std::{collections::BTreeSet, io::Write};
+
+use common::{
+    checkpoint::{self, Progress, Saved, Schedule, State},
+    geom::{Direction, Point},
+    image::{Colour, Image},
+    *,
+};
+
+pub const DAY: Day = Day::new::<Puzzle>(2022, 9, "rope_bridge", env!("CARGO_MANIFEST_DIR"));
+
+common::example_tests!(DAY);
+
+fn parse_direction(value: &str) -> Result<Direction> {
+    let direction = match value {
+        "U" => Direction::Up,
+        "D" => Direction::Down,
+        "L" => Direction::Left,
+        "R" => Direction::Right,
+        _ => {
+            return Err(Failure::parse(format!(
+                r#"Invalid &str for a Direction: "{value}""#
+            )))
+        }
+    };
+
+    Ok(direction)
+}
+
+/// Moving the head of the rope `steps` squares in `direction`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Motion {
+    pub direction: Direction,
+    pub steps: u32,
+}
+
+impl Motion {
+    /// How many squares the head moves, one step each
+    fn length(&self) -> usize {
+        self.steps as usize
+    }
+
+    /// Reads `<U|D|L|R> <steps>`.
+    pub fn scan(s: &mut Scanner) -> Result<Self> {
+        let direction = s.word_as(parse_direction)?;
+        s.literal(" ")?;
+        let steps = s.int()?;
+
+        Ok(Self { direction, steps })
+    }
+}
+
+/// Where a knot ends up after the knot in front of it moves to `head`
+pub fn follow(head: Point, knot: Point) -> Point {
+    if knot.touches(head) {
+        knot
+    } else {
+        knot + (head - knot).signum()
+    }
+}
+
+/// A rope of knots, head first, and every square its tail has visited
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rope {
+    knots: Vec<Point>,
+    visited: BTreeSet<Point>,
+}
+
+impl Rope {
+    /// `knots` knots, all at the start. A rope needs at least one.
+    pub fn new(knots: usize) -> Result<Self> {
+        if knots == 0 {
+            return Err(Failure::validation("A rope needs at least one knot"));
+        }
+
+        Ok(Self {
+            knots: vec![Point::ORIGIN; knots],
+            visited: BTreeSet::from([Point::ORIGIN]),
+        })
+    }
+
+    pub fn knots(&self) -> &[Point] {
+        &self.knots
+    }
+
+    /// The squares the tail has visited, sorted.
+    pub fn visited(&self) -> &BTreeSet<Point> {
+        &self.visited
+    }
+
+    /// Moves the head one square in `direction`, with every other knot
+    /// following the one in front of it.
+    pub fn pull(&mut self, direction: Direction) {
+        // Neither `new` nor `restore` makes a rope without knots
+        let mut cursor = self.knots[0].step(direction);
+        self.knots[0] = cursor;
+
+        for knot in self.knots.iter_mut().skip(1) {
+            *knot = follow(cursor, *knot);
+            cursor = *knot;
+        }
+        self.visited.insert(cursor);
+    }
+}
+
+fn scan_point(s: &mut Scanner) -> Result<Point> {
+    let x = s.int()?;
+    s.literal(",")?;
+    let y = s.int()?;
+    Ok(Point::new(x, y))
+}
+
+/// A `Knot` line for each knot, head first, then a `Visited` line for each
+/// square the tail has been to, sorted.
+impl State for Rope {
+    fn save(&self) -> String {
+        let knots = self.knots.iter().map(|knot| ("Knot", knot));
+        let visited = self.visited.iter().map(|square| ("Visited", square));
+
+        knots
+            .chain(visited)
+            .map(|(key, point)| format!("{key}: {},{}\n", point.x, point.y))
+            .collect()
+    }
+
+    fn restore(s: Scanner) -> Result<Self> {
+        let mut rope = Self {
+            knots: vec![],
+            visited: BTreeSet::new(),
+        };
+
+        for mut line in s.lines() {
+            let start = line;
+            let (key, value) = line.key_value(":")?;
+            let point = value.parse_all(scan_point)?;
+            match key {
+                "Knot" if rope.visited.is_empty() => rope.knots.push(point),
+                "Visited" => {
+                    rope.visited.insert(point);
+                }
+                key => return Err(start.error_at(key, format!("Unexpected {key:?} line"))),
+            }
+        }
+
+        if rope.knots.is_empty() {
+            return Err(s.error_at_end("Expected at least one knot"));
+        }
+
+        Ok(rope)
+    }
+}
+
+/// How many squares the last of `knots` knots visits while the head makes
+/// `motions`.
+pub fn count_tail_positions(motions: &[Motion], knots: usize) -> Result<usize> {
+    Ok(pull_rope(motions, knots, None)?.visited.len())
+}
+
+/// How much of the rope is shown around its head
+const VIEW: (i64, i64) = (40, 12);
+
+/// The rope around its head, with the squares its tail has visited
+fn draw_rope(rope: &Rope) -> String {
+    let head = rope.knots[0];
+    let mut view = String::new();
+
+    for y in head.y - VIEW.1..=head.y + VIEW.1 {
+        for x in head.x - VIEW.0..=head.x + VIEW.0 {
+            let point = Point::new(x, y);
+            let square = match rope.knots.iter().position(|knot| *knot == point) {
+                Some(0) => 'H',
+                Some(knot) if knot == rope.knots.len() - 1 => 'T',
+                Some(knot) => char::from_digit(knot as u32 % 10, 10).unwrap_or('?'),
+                None if point == Point::ORIGIN => 's',
+                None if rope.visited.contains(&point) => '#',
+                None => '.',
+            };
+            view.push(square);
+        }
+        view.push('\n');
+    }
+
+    view
+}
+
+/// The rope once the head has made every one of `motions`.
+fn pull_rope(
+    motions: &[Motion],
+    knots: usize,
+    mut animation: Option<&mut Animation>,
+) -> Result<Rope> {
+    let mut rope = Rope::new(knots)?;
+    let mut moves = Schedule::new(motions, Motion::length);
+
+    checkpoint::finish(&mut rope, |rope, step| {
+        Ok(pull_step(rope, &mut moves, step, animation.as_deref_mut()))
+    })?;
+
+    Ok(rope)
+}
+
+/// Moves the head for the step numbered `step`, or returns `false` once
+/// every motion has been made.
+fn pull_step(
+    rope: &mut Rope,
+    moves: &mut Schedule<Motion>,
+    step: usize,
+    animation: Option<&mut Animation>,
+) -> bool {
+    let Some((i, motion, taken)) = moves.at(step) else {
+        return false;
+    };
+    rope.pull(motion.direction);
+
+    if let Some(animation) = animation.filter(|a| a.drawing()) {
+        animation.frame(
+            format_args!(
+                "Motion {}/{}: {:?} {}/{}",
+                i + 1,
+                moves.len(),
+                motion.direction,
+                taken + 1,
+                motion.steps
+            ),
+            draw_rope(rope),
+        );
+    }
+
+    true
+}
+
+/// The squares in `visited`, cropped to fit, with the start marked.
+fn draw_trail(visited: &BTreeSet<Point>) -> Image {
+    let corner = |pick: fn(i64, i64) -> i64| {
+        visited
+            .iter()
+            .chain([&Point::ORIGIN])
+            .fold(Point::ORIGIN, |corner, point| {
+                Point::new(pick(corner.x, point.x), pick(corner.y, point.y))
+            })
+    };
+    let (min, max) = (corner(i64::min), corner(i64::max));
+    let pixel = |point: Point| ((point.x - min.x) as usize, (point.y - min.y) as usize);
+
+    let (width, height) = pixel(max);
+    let mut image = Image::new(width + 1, height + 1, Colour::rgb(20, 20, 30));
+    for &point in visited {
+        let (x, y) = pixel(point);
+        image.set(x, y, Colour::rgb(240, 180, 60));
+    }
+    let (x, y) = pixel(Point::ORIGIN);
+    image.set(x, y, Colour::rgb(60, 200, 240));
+
+    image
+}
+
+/// How long the rope is in each part
+fn knots(part: Part) -> usize {
+    match part {
+        Part::One => 2,
+        Part::Two => 10,
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<Motion>;
+
+    fn parse(data: &str) -> Result<Self::Parsed> {
+        Scanner::new(data)
+            .lines()
+            .map(|line| line.parse_all(Motion::scan))
+            .collect()
+    }
+
+    fn part_one(motions: &Self::Parsed) -> Result<Answer> {
+        Ok(count_tail_positions(motions, 2)?.into())
+    }
+
+    fn part_two(motions: &Self::Parsed) -> Result<Answer> {
+        Ok(count_tail_positions(motions, 10)?.into())
+    }
+
+    /// The rope moving, with the tail's trail behind it.
+    fn animate(motions: &Self::Parsed, part: Part, animation: &mut Animation) -> Result<Answer> {
+        Ok(pull_rope(motions, knots(part), Some(animation))?
+            .visited
+            .len()
+            .into())
+    }
+
+    /// The trail of squares the tail visited.
+    fn render(motions: &Self::Parsed, part: Part) -> Result<Vec<Image>> {
+        Ok(vec![draw_trail(
+            &pull_rope(motions, knots(part), None)?.visited,
+        )])
+    }
+
+    /// A step is the head moving one square.
+    fn simulate(
+        motions: &Self::Parsed,
+        part: Part,
+        from: Option<Saved>,
+        until: Option<usize>,
+    ) -> Result<Progress> {
+        let mut moves = Schedule::new(motions, Motion::length);
+        let rope = Rope::new(knots(part))?;
+
+        checkpoint::run(
+            from,
+            || rope,
+            until,
+            |rope, step| Ok(pull_step(rope, &mut moves, step, None)),
+            |rope| rope.visited.len().into(),
+        )
+    }
+
+    fn generate(rng: &mut Rng, out: &mut Output) -> std::io::Result<()> {
+        loop {
+            let direction = rng.pick(&['U', 'D', 'L', 'R']);
+            writeln!(out, "{direction} {}", rng.between(1, 20))?;
+            if out.full() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::{checkpoint::State, geom::Direction, geom::Point, Result, Scanner};
+
+    use super::{Rope, DAY};
+
+    #[test]
+    fn follow() {
+        let cases = [
+            ((0, 0), (0, 0)),
+            ((1, 1), (0, 0)),
+            ((2, 2), (1, 1)),
+            ((-2, -2), (-1, -1)),
+            ((2, 1), (1, 1)),
+            ((1, 2), (1, 1)),
+            ((-2, 1), (-1, 1)),
+            ((1, -2), (1, -1)),
+        ];
+
+        for (head, expected) in cases {
+            let knot = super::follow(head.into(), Point::ORIGIN);
+            assert_eq!(knot, expected.into(), "following {head:?}");
+        }
+    }
+
+    #[test]
+    fn state_round_trips() -> Result<()> {
+        let mut rope = Rope::new(3)?;
+        rope.pull(Direction::Right);
+        rope.pull(Direction::Right);
+        rope.pull(Direction::Right);
+        let state = rope.save();
+
+        assert_eq!(
+            state,
+            "Knot: 3,0\nKnot: 2,0\nKnot: 1,0\nVisited: 0,0\nVisited: 1,0\n"
+        );
+        assert_eq!(Rope::restore(Scanner::new(&state))?, rope);
+        assert!(Rope::restore(Scanner::new("Visited: 0,0\nKnot: 1,0\n")).is_err());
+        assert!(Rope::new(0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn resumes() {
+        if let Err(err) = common::examples::check_resumed(&DAY) {
+            panic!("{}", err.report());
+        }
+    }
+}
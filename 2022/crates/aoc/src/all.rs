@@ -0,0 +1,175 @@
+//! `aoc all`, which solves every day at once, each on its own thread.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use common::*;
+
+use crate::{
+    bench,
+    check::summarise,
+    colour::{self, Colour},
+    guard::{self, Attempt},
+};
+
+pub enum Outcome {
+    NoInput,
+    /// Panics and timeouts are kept in the attempt as failures
+    Ran(Attempt),
+}
+
+pub struct DayRun {
+    pub day: &'static Day,
+    pub outcome: Outcome,
+    /// Reading the input as well as solving
+    pub time: Duration,
+}
+
+impl DayRun {
+    pub fn failed(&self) -> bool {
+        match &self.outcome {
+            Outcome::NoInput => false,
+            Outcome::Ran(attempt) => match &attempt.parts {
+                Ok(parts) => parts.iter().any(|(_, answer, _)| answer.is_err()),
+                Err(_) => true,
+            },
+        }
+    }
+}
+
+fn run_day(day: &'static Day, parts: &[Part], limit: Option<Duration>) -> Result<Outcome> {
+    let Some(path) = input_file(day) else {
+        return Ok(Outcome::NoInput);
+    };
+    let input = std::fs::read_to_string(&path)
+        .map_err(|err| Failure::io(format!("Couldn't read {}", path.display()), err))?;
+
+    Ok(Outcome::Ran(guard::attempt(day, &input, parts, limit)))
+}
+
+/// Solves `parts` of every one of `days`, in parallel. One day failing,
+/// panicking or running past `limit` doesn't stop the others.
+pub fn run_all(days: &[&'static Day], parts: &[Part], limit: Option<Duration>) -> Vec<DayRun> {
+    thread::scope(|scope| {
+        let handles = days
+            .iter()
+            .map(|&day| {
+                let handle = scope.spawn(move || {
+                    let start = Instant::now();
+                    let outcome = run_day(day, parts, limit);
+                    (outcome, start.elapsed())
+                });
+                (day, handle)
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|(day, handle)| {
+                // The solver runs under `guard::attempt`, which turns its
+                // panics into failures
+                let (outcome, time) = handle.join().expect("run_day panicked");
+                let outcome = outcome.unwrap_or_else(|err| {
+                    Outcome::Ran(Attempt {
+                        parse_time: Duration::ZERO,
+                        parts: Err(err),
+                    })
+                });
+                DayRun { day, outcome, time }
+            })
+            .collect()
+    })
+}
+
+/// What goes in a part's column.
+fn cell(run: &DayRun, part: Part) -> String {
+    let Outcome::Ran(attempt) = &run.outcome else {
+        return String::new();
+    };
+
+    match &attempt.parts {
+        Ok(parts) => parts
+            .iter()
+            .find(|(p, _, _)| *p == part)
+            .map(|(_, answer, _)| match answer {
+                Ok(answer) => summarise(&answer.to_string()),
+                Err(_) => "-".to_owned(),
+            })
+            .unwrap_or_default(),
+        Err(_) => "-".to_owned(),
+    }
+}
+
+/// The first failure, for the line under the table.
+fn failure(run: &DayRun) -> Option<String> {
+    match &run.outcome {
+        Outcome::NoInput => None,
+        Outcome::Ran(attempt) => match &attempt.parts {
+            Err(err) => Some(err.report()),
+            Ok(parts) => parts.iter().find_map(|(part, answer, _)| {
+                answer
+                    .as_ref()
+                    .err()
+                    .map(|err| format!("{part}: {}", err.report()))
+            }),
+        },
+    }
+}
+
+pub fn print_table(runs: &[DayRun], wall: Duration) {
+    let width = |part| {
+        runs.iter()
+            .map(|run| cell(run, part).chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(8)
+    };
+    let (one, two) = (width(Part::One), width(Part::Two));
+
+    println!(
+        "{:<4} {:>3}  {:<one$}  {:<two$}  {:<8}  {:>8}",
+        "Year", "Day", "Part one", "Part two", "Status", "Time"
+    );
+
+    for run in runs {
+        let (status, colour) = match (&run.outcome, run.failed()) {
+            (Outcome::NoInput, _) => ("no input", Colour::Yellow),
+            (_, true) => ("FAILED", Colour::Red),
+            (_, false) => ("ok", Colour::Green),
+        };
+        let time = match run.outcome {
+            Outcome::NoInput => String::new(),
+            _ => bench::format_duration(run.time),
+        };
+        let line = format!(
+            "{:<4} {:>3}  {:<one$}  {:<two$}  {}  {:>8}",
+            run.day.year,
+            run.day.day,
+            cell(run, Part::One),
+            cell(run, Part::Two),
+            colour::paint(&format!("{status:<8}"), colour),
+            time,
+        );
+        println!("{}", line.trim_end());
+    }
+
+    let total = runs.iter().map(|run| run.time).sum::<Duration>();
+    let ran = runs
+        .iter()
+        .filter(|run| !matches!(run.outcome, Outcome::NoInput))
+        .count();
+    let failed = runs.iter().filter(|run| run.failed()).count();
+    println!(
+        "\nTotal: {} across {ran} days ({} wall clock), {failed} failed",
+        bench::format_duration(total),
+        bench::format_duration(wall)
+    );
+
+    for run in runs {
+        if let Some(failure) = failure(run) {
+            println!("\n{} day {}: {failure}", run.day.year, run.day.day);
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! Accepted answers, kept in a small subset of TOML so they can be read and
+//! diffed by hand:
+//!
+//! ```toml
+//! [2022.1]
+//! part_one = 72240
+//! part_two = "text"
+//!
+//! [2022.10]
+//! part_two = '''
+//! multi-line
+//! text
+//! '''
+//! ```
+
+use std::{collections::BTreeMap, fs, io::ErrorKind, path::Path};
+
+use common::*;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct AnswerStore(BTreeMap<(u16, u8, Part), Answer>);
+
+impl AnswerStore {
+    /// An empty store if `path` doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                Self::parse(&text).map_err(|err| err.context(format!("In {}", path.display())))
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(Failure::io(
+                format!("Couldn't read {}", path.display()),
+                err,
+            )),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_toml())
+            .map_err(|err| Failure::io(format!("Couldn't write {}", path.display()), err))
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: Part) -> Option<&Answer> {
+        self.0.get(&(year, day, part))
+    }
+
+    pub fn insert(&mut self, year: u16, day: u8, part: Part, answer: Answer) {
+        self.0.insert((year, day, part), answer);
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let text = normalize(text);
+        let mut scanner = Scanner::new(&text);
+        let mut store = Self::default();
+        let mut table = None;
+
+        loop {
+            scanner.skip_whitespace();
+            match scanner.peek() {
+                None => break,
+                Some('\n') => {
+                    scanner.char()?;
+                    continue;
+                }
+                Some('#') => {}
+                Some('[') => table = Some(scan_header(&mut scanner)?),
+                Some(_) => {
+                    let key = scanner.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+                    let part = match key {
+                        "part_one" => Part::One,
+                        "part_two" => Part::Two,
+                        "" => return Err(scanner.error("Expected a key")),
+                        key => {
+                            return Err(scanner.error_at(
+                                key,
+                                format!("Unknown key {key:?}, expected part_one or part_two"),
+                            ))
+                        }
+                    };
+                    let (year, day) = table.ok_or_else(|| {
+                        scanner.error_at(key, "Expected a [year.day] table before any answers")
+                    })?;
+
+                    scanner.skip_whitespace();
+                    scanner.literal("=")?;
+                    scanner.skip_whitespace();
+                    let answer = scan_value(&mut scanner)?;
+
+                    if store.0.insert((year, day, part), answer).is_some() {
+                        return Err(scanner
+                            .error_at(key, format!("{part} of {year} day {day} is listed twice")));
+                    }
+                }
+            }
+
+            scanner.skip_whitespace();
+            if scanner.peek() == Some('#') {
+                scanner.take_while(|c| c != '\n');
+            }
+            if !scanner.is_empty() {
+                scanner.literal("\n")?;
+            }
+        }
+
+        Ok(store)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut toml = "# Accepted answers, written by `aoc --record`\n".to_owned();
+        let mut table = None;
+
+        for (&(year, day, part), answer) in &self.0 {
+            if table != Some((year, day)) {
+                toml.push_str(&format!("\n[{year}.{day}]\n"));
+                table = Some((year, day));
+            }
+            let key = match part {
+                Part::One => "part_one",
+                Part::Two => "part_two",
+            };
+            toml.push_str(&format!("{key} = {}\n", format_value(answer)));
+        }
+
+        toml
+    }
+}
+
+fn scan_header(scanner: &mut Scanner) -> Result<(u16, u8)> {
+    scanner.literal("[")?;
+    let year = scanner.int()?;
+    scanner.literal(".")?;
+    let day = scanner.int()?;
+    scanner.literal("]")?;
+    Ok((year, day))
+}
+
+fn scan_value(scanner: &mut Scanner) -> Result<Answer> {
+    if scanner.try_literal("'''") {
+        // Like TOML, a newline straight after the opening quotes is dropped
+        scanner.try_literal("\n");
+        let text = scanner.until("'''")?.rest();
+        return Ok(Answer::Text(
+            text.strip_suffix('\n').unwrap_or(text).to_owned(),
+        ));
+    }
+
+    if scanner.try_literal("\"") {
+        let mut text = String::new();
+        loop {
+            match scanner.char()? {
+                '"' => return Ok(Answer::Text(text)),
+                '\\' => text.push(match scanner.char()? {
+                    'n' => '\n',
+                    't' => '\t',
+                    '"' => '"',
+                    '\\' => '\\',
+                    _ => return Err(scanner.error("Unsupported escape")),
+                }),
+                '\n' => return Err(scanner.error("Unterminated string")),
+                c => text.push(c),
+            }
+        }
+    }
+
+    Ok(Answer::Number(scanner.int()?))
+}
+
+fn format_value(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(number) => number.to_string(),
+        Answer::Text(text) if text.contains('\n') && !text.contains("'''") => {
+            format!("'''\n{text}\n'''")
+        }
+        Answer::Text(text) => {
+            let mut quoted = "\"".to_owned();
+            for c in text.chars() {
+                match c {
+                    '\n' => quoted.push_str("\\n"),
+                    '\t' => quoted.push_str("\\t"),
+                    '"' | '\\' => {
+                        quoted.push('\\');
+                        quoted.push(c);
+                    }
+                    c => quoted.push(c),
+                }
+            }
+            quoted.push('"');
+            quoted
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AnswerStore;
+    use common::{fuzz, Answer, Part, Result};
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        let mut store = AnswerStore::default();
+        store.insert(2022, 10, Part::Two, Answer::Text("##..\n.##.".to_owned()));
+        store.insert(2022, 5, Part::One, Answer::Text("C\"M\\Z".to_owned()));
+        store.insert(2022, 1, Part::One, Answer::Number(72240));
+        store.insert(2022, 1, Part::Two, Answer::Number(-3));
+
+        let toml = store.to_toml();
+        assert!(toml.contains("[2022.1]\npart_one = 72240\npart_two = -3\n"));
+        assert!(toml.contains("part_two = '''\n##..\n.##.\n'''\n"));
+        assert_eq!(AnswerStore::parse(&toml)?, store);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse() -> Result<()> {
+        let store = AnswerStore::parse(
+            "# comment\n[2022.6]\n  part_one = 7 # trailing comment\r\n\npart_two = \"a b\"\n",
+        )?;
+        assert_eq!(store.get(2022, 6, Part::One), Some(&Answer::Number(7)));
+        assert_eq!(store.get(2022, 6, Part::Two), Some(&Answer::from("a b")));
+        assert_eq!(store.get(2022, 7, Part::One), None);
+
+        assert!(AnswerStore::parse("part_one = 1").is_err());
+        assert!(AnswerStore::parse("[2022.1]\npart_three = 1").is_err());
+        assert!(AnswerStore::parse("[2022.1]\npart_one = 1\npart_one = 2").is_err());
+        assert!(AnswerStore::parse("[2022.1]\npart_one = \"open").is_err());
+        assert!(AnswerStore::parse("[2022.1]\npart_one = 1 2").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fuzz_round_trip() -> Result<()> {
+        let mut store = AnswerStore::default();
+        store.insert(2022, 10, Part::Two, Answer::Text("##..\n.##.".to_owned()));
+        store.insert(2022, 5, Part::One, Answer::Text("C\"M\\Z".to_owned()));
+        store.insert(2022, 1, Part::One, Answer::Number(72240));
+        let seeds = [store.to_toml()];
+
+        fuzz::check_round_trip(
+            &fuzz::dir(env!("CARGO_MANIFEST_DIR"), "answers"),
+            &seeds,
+            AnswerStore::parse,
+            AnswerStore::to_toml,
+        )
+    }
+}
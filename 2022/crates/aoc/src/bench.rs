@@ -0,0 +1,211 @@
+use std::{fmt::Display, fs, path::Path, time::Duration};
+
+use common::*;
+
+use crate::guard::{self, Attempt};
+
+/// A timed step of solving a day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "Parse"),
+            Phase::Solve(part) => write!(f, "{part}"),
+        }
+    }
+}
+
+impl Phase {
+    /// Name used in the machine-readable summary
+    fn key(&self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Solve(Part::One) => "part_one",
+            Phase::Solve(Part::Two) => "part_two",
+        }
+    }
+}
+
+pub type Timings = Vec<(Phase, Duration)>;
+
+/// Each phase of `attempt` that finished.
+pub fn timings(attempt: &Attempt) -> Timings {
+    let mut timings = vec![(Phase::Parse, attempt.parse_time)];
+    if let Ok(parts) = &attempt.parts {
+        timings.extend(
+            parts
+                .iter()
+                .filter(|(_, answer, _)| answer.is_ok())
+                .map(|(part, _, time)| (Phase::Solve(*part), *time)),
+        );
+    }
+    timings
+}
+
+/// Solves `parts` once on guarded threads, timing each phase.
+fn time(
+    day: &'static Day,
+    input: &str,
+    parts: &[Part],
+    limit: Option<Duration>,
+) -> Result<Timings> {
+    let attempt = guard::attempt(day, input, parts, limit);
+    let timings = timings(&attempt);
+    attempt.answers()?;
+
+    Ok(timings)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Panics if `samples` is empty.
+    fn of(samples: &mut [Duration]) -> Self {
+        samples.sort();
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        Self {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Warms up, then times `runs` full solves of `parts`, each phase of each
+/// run given up on after `limit`.
+pub fn bench(
+    day: &'static Day,
+    input: &str,
+    parts: &[Part],
+    runs: usize,
+    limit: Option<Duration>,
+) -> Result<Vec<(Phase, Stats)>> {
+    for _ in 0..(runs / 10).max(1) {
+        time(day, input, parts, limit)?;
+    }
+
+    let mut samples: Vec<(Phase, Vec<Duration>)> = vec![];
+
+    for _ in 0..runs {
+        let timings = time(day, input, parts, limit)?;
+        for (phase, duration) in timings {
+            match samples.iter_mut().find(|(p, _)| *p == phase) {
+                Some((_, durations)) => durations.push(duration),
+                None => samples.push((phase, vec![duration])),
+            }
+        }
+    }
+
+    Ok(samples
+        .into_iter()
+        .map(|(phase, mut durations)| (phase, Stats::of(&mut durations)))
+        .collect())
+}
+
+/// Formats with three significant figures and the largest fitting unit.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    let (value, unit) = match nanos {
+        n if n >= 1e9 => (n / 1e9, "s"),
+        n if n >= 1e6 => (n / 1e6, "ms"),
+        n if n >= 1e3 => (n / 1e3, "µs"),
+        n => (n, "ns"),
+    };
+    let decimals = match value {
+        v if v >= 100. => 0,
+        v if v >= 10. => 1,
+        _ => 2,
+    };
+    format!("{value:.decimals$}{unit}")
+}
+
+pub fn print_timings(timings: &[(Phase, Duration)]) {
+    for (phase, duration) in timings {
+        println!("{phase} took {}", format_duration(*duration));
+    }
+}
+
+pub fn print_stats(runs: usize, stats: &[(Phase, Stats)]) {
+    println!("{runs} runs");
+    println!("{:<10} {:>10} {:>10} {:>10}", "", "min", "median", "max");
+    for (phase, stats) in stats {
+        println!(
+            "{:<10} {:>10} {:>10} {:>10}",
+            phase.to_string(),
+            format_duration(stats.min),
+            format_duration(stats.median),
+            format_duration(stats.max)
+        );
+    }
+}
+
+/// One CSV row per phase, in nanoseconds, so runs from different commits can
+/// be diffed or loaded into a spreadsheet.
+pub fn summary(day: &Day, runs: usize, stats: &[(Phase, Stats)]) -> String {
+    let mut summary = "year,day,phase,runs,min_ns,median_ns,max_ns\n".to_owned();
+    for (phase, stats) in stats {
+        summary.push_str(&format!(
+            "{},{},{},{runs},{},{},{}\n",
+            day.year,
+            day.day,
+            phase.key(),
+            stats.min.as_nanos(),
+            stats.median.as_nanos(),
+            stats.max.as_nanos()
+        ));
+    }
+    summary
+}
+
+pub fn write_summary(path: &Path, summary: &str) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .map_err(|err| Failure::io(format!("Couldn't create {}", parent.display()), err))?;
+    }
+
+    fs::write(path, summary)
+        .map_err(|err| Failure::io(format!("Couldn't write {}", path.display()), err))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{format_duration, Stats};
+
+    #[test]
+    fn stats() {
+        let mut samples = [5, 1, 4, 2, 3].map(Duration::from_millis);
+        let stats = Stats::of(&mut samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+
+        let mut samples = [4, 1, 2, 3].map(Duration::from_millis);
+        assert_eq!(Stats::of(&mut samples).median, Duration::from_micros(2500));
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(format_duration(Duration::from_nanos(512)), "512ns");
+        assert_eq!(format_duration(Duration::from_nanos(1_234)), "1.23µs");
+        assert_eq!(format_duration(Duration::from_micros(45_670)), "45.7ms");
+        assert_eq!(format_duration(Duration::from_secs(2)), "2.00s");
+    }
+}
@@ -0,0 +1,146 @@
+use common::*;
+
+use crate::{
+    answers::AnswerStore,
+    colour::{self, Colour},
+    guard::Attempt,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Error,
+    /// Solved, but there's no accepted answer to compare with
+    Unrecorded,
+    NoInput,
+}
+
+impl Status {
+    fn label(self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Error => "ERROR",
+            Status::Unrecorded => "new",
+            Status::NoInput => "no input",
+        }
+    }
+
+    fn colour(self) -> Colour {
+        match self {
+            Status::Pass => Colour::Green,
+            Status::Fail | Status::Error => Colour::Red,
+            Status::Unrecorded | Status::NoInput => Colour::Yellow,
+        }
+    }
+
+    pub fn is_failure(self) -> bool {
+        matches!(self, Status::Fail | Status::Error)
+    }
+}
+
+/// The outcome of checking one part of one day.
+pub struct Row {
+    pub day: &'static Day,
+    pub part: Part,
+    pub status: Status,
+    pub detail: String,
+}
+
+/// Keeps tables to one line per answer.
+pub fn summarise(answer: &str) -> String {
+    match answer.split_once('\n') {
+        Some((first, _)) => format!(
+            "{}… ({} lines)",
+            first.chars().take(16).collect::<String>(),
+            answer.lines().count()
+        ),
+        None => answer.to_owned(),
+    }
+}
+
+/// Compares each of `parts` of `day` against the store. `attempt` is `None`
+/// when the day has no input to solve.
+pub fn check(
+    store: &AnswerStore,
+    day: &'static Day,
+    attempt: Option<Attempt>,
+    parts: &[Part],
+) -> Vec<Row> {
+    let row = |part, status, detail| Row {
+        day,
+        part,
+        status,
+        detail,
+    };
+
+    let Some(attempt) = attempt else {
+        return parts
+            .iter()
+            .map(|&part| row(part, Status::NoInput, String::new()))
+            .collect();
+    };
+
+    let answers = match attempt.parts {
+        Ok(answers) => answers,
+        Err(err) => {
+            return parts
+                .iter()
+                .map(|&part| row(part, Status::Error, err.message().to_owned()))
+                .collect()
+        }
+    };
+
+    answers
+        .into_iter()
+        .map(|(part, answer, _)| {
+            let (status, detail) = match (answer, store.get(day.year, day.day, part)) {
+                (Err(err), _) => (Status::Error, err.message().to_owned()),
+                (Ok(answer), None) => (Status::Unrecorded, summarise(&answer.to_string())),
+                (Ok(answer), Some(expected)) if answer.to_string() == expected.to_string() => {
+                    (Status::Pass, summarise(&answer.to_string()))
+                }
+                (Ok(answer), Some(expected)) => (
+                    Status::Fail,
+                    format!(
+                        "expected {}, got {}",
+                        summarise(&expected.to_string()),
+                        summarise(&answer.to_string())
+                    ),
+                ),
+            };
+            row(part, status, detail)
+        })
+        .collect()
+}
+
+pub fn print_table(rows: &[Row]) {
+    let paint = |status: Status| colour::paint(&format!("{:<8}", status.label()), status.colour());
+
+    println!(
+        "{:<4} {:>3}  {:<8}  {:<8}  Answer",
+        "Year", "Day", "Part", "Status"
+    );
+    for row in rows {
+        let line = format!(
+            "{:<4} {:>3}  {:<8}  {}  {}",
+            row.day.year,
+            row.day.day,
+            row.part.to_string(),
+            paint(row.status),
+            row.detail
+        );
+        println!("{}", line.trim_end());
+    }
+
+    let count = |status| rows.iter().filter(|row| row.status == status).count();
+    println!(
+        "\n{} passed, {} failed, {} errors, {} new, {} without input",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Error),
+        count(Status::Unrecorded),
+        count(Status::NoInput)
+    );
+}
@@ -0,0 +1,22 @@
+use std::io::IsTerminal;
+
+/// ANSI foreground colours
+#[derive(Debug, Clone, Copy)]
+pub enum Colour {
+    Red = 31,
+    Green = 32,
+    Yellow = 33,
+}
+
+/// Only when stdout is a terminal, and `NO_COLOR` isn't set.
+pub fn enabled() -> bool {
+    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+}
+
+pub fn paint(text: &str, colour: Colour) -> String {
+    if enabled() {
+        format!("\x1b[{}m{text}\x1b[0m", colour as u8)
+    } else {
+        text.to_owned()
+    }
+}
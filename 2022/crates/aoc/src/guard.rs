@@ -0,0 +1,215 @@
+//! Runs solvers on their own threads, so that a panic or an endless loop in
+//! one of them becomes a failure instead of taking the runner down with it.
+
+use std::{
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, Instant},
+};
+
+use common::*;
+
+/// Runs `f` on a new thread, giving up on it after `limit`. The thread can't
+/// be stopped, so one that's timed out carries on in the background until
+/// the runner exits.
+fn guarded<T: Send + 'static>(
+    step: Step,
+    limit: Option<Duration>,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Result<(T, Duration)> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let start = Instant::now();
+        let result = catch_panic(f).map(|value| (value, start.elapsed()));
+        // The runner may have stopped waiting
+        let _ = sender.send(result);
+    });
+
+    let result = match limit {
+        Some(limit) => receiver
+            .recv_timeout(limit)
+            .map_err(|_| Failure::timeout(step, limit))?,
+        None => receiver
+            .recv()
+            .map_err(|_| e!("{step} stopped without a result"))?,
+    };
+
+    result.map_err(|message| Failure::panic(step, message))
+}
+
+/// Every requested part of a day, timed, with failures kept rather than
+/// stopping at the first one.
+pub struct Attempt {
+    pub parse_time: Duration,
+    /// The parse failure, or each part's answer or failure and how long it
+    /// took
+    pub parts: Result<Vec<(Part, Result<Answer>, Duration)>>,
+}
+
+impl Attempt {
+    /// The answers, or the first failure.
+    pub fn answers(self) -> Result<Vec<(Part, Answer)>> {
+        self.parts?
+            .into_iter()
+            .map(|(part, answer, _)| Ok((part, answer?)))
+            .collect()
+    }
+}
+
+/// Parses `input` on a guarded thread, giving up on it after `limit`.
+pub fn parse(day: &'static Day, input: &str, limit: Option<Duration>) -> Result<Parsed> {
+    let input: Arc<str> = input.into();
+    let step = Step {
+        year: day.year,
+        day: day.day,
+        part: None,
+    };
+
+    guarded(step, limit, move || day.parse(&input))?.0
+}
+
+/// Runs `f`, which works on `part` of `day`, on a guarded thread, giving up
+/// on it after `limit`.
+pub fn run<T: Send + 'static>(
+    day: &'static Day,
+    part: Part,
+    limit: Option<Duration>,
+    f: impl FnOnce() -> Result<T> + Send + 'static,
+) -> Result<T> {
+    let step = Step {
+        year: day.year,
+        day: day.day,
+        part: Some(part),
+    };
+
+    guarded(step, limit, f)?.0
+}
+
+/// Parses `input` and solves each of `parts`, each step on a guarded thread
+/// with its own time `limit`. A part that fails doesn't stop the next one
+/// from being tried.
+pub fn attempt(day: &'static Day, input: &str, parts: &[Part], limit: Option<Duration>) -> Attempt {
+    let input: Arc<str> = input.into();
+    let step = |part| Step {
+        year: day.year,
+        day: day.day,
+        part,
+    };
+    let parse = || {
+        let input = input.clone();
+        let start = Instant::now();
+        match guarded(step(None), limit, move || day.parse(&input)) {
+            Ok((parsed, time)) => (parsed, time),
+            Err(failure) => (Err(failure), start.elapsed()),
+        }
+    };
+
+    let (parsed, parse_time) = parse();
+    let mut parsed = match parsed {
+        Ok(parsed) => Some(parsed),
+        Err(failure) => {
+            return Attempt {
+                parse_time,
+                parts: Err(failure),
+            }
+        }
+    };
+
+    let mut results = vec![];
+    for &part in parts {
+        // A part that was abandoned took the parsed input with it
+        let input = match parsed.take() {
+            Some(input) => input,
+            None => match parse().0 {
+                Ok(input) => input,
+                Err(failure) => {
+                    results.push((part, Err(failure), Duration::ZERO));
+                    continue;
+                }
+            },
+        };
+
+        let start = Instant::now();
+        match guarded(step(Some(part)), limit, move || {
+            let answer = day.part(part, &input);
+            (answer, input)
+        }) {
+            Ok(((answer, input), time)) => {
+                parsed = Some(input);
+                results.push((part, answer, time));
+            }
+            Err(failure) => results.push((part, Err(failure), start.elapsed())),
+        }
+    }
+
+    Attempt {
+        parse_time,
+        parts: Ok(results),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use common::*;
+
+    use super::{attempt, run};
+
+    struct Unruly;
+
+    impl Solution for Unruly {
+        type Parsed = u64;
+
+        fn parse(input: &str) -> Result<Self::Parsed> {
+            Ok(input.len() as u64)
+        }
+
+        fn part_one(_: &Self::Parsed) -> Result<Answer> {
+            panic!("Malformed game")
+        }
+
+        fn part_two(length: &Self::Parsed) -> Result<Answer> {
+            if *length > 3 {
+                loop {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+            }
+            Ok((*length).into())
+        }
+    }
+
+    const UNRULY: Day = Day::new::<Unruly>(2022, 99, "unruly", env!("CARGO_MANIFEST_DIR"));
+
+    #[test]
+    fn panics_and_timeouts() {
+        let limit = Some(Duration::from_millis(100));
+        let parts = attempt(&UNRULY, "abcd", &[Part::One, Part::Two], limit)
+            .parts
+            .expect("parsing doesn't fail");
+
+        let Err(Failure::Panic { message, step }) = &parts[0].1 else {
+            panic!("expected a panic, got {:?}", parts[0].1);
+        };
+        assert!(message.starts_with("Malformed game (at "));
+        assert_eq!(step.part, Some(Part::One));
+
+        let Err(Failure::Timeout {
+            step, limit: after, ..
+        }) = &parts[1].1
+        else {
+            panic!("expected a timeout, got {:?}", parts[1].1);
+        };
+        assert_eq!(step.part, Some(Part::Two));
+        assert_eq!(*after, Duration::from_millis(100));
+
+        let answers = attempt(&UNRULY, "abc", &[Part::Two], limit).answers();
+        assert_eq!(answers.ok(), Some(vec![(Part::Two, Answer::Number(3))]));
+
+        let rendered = run(&UNRULY, Part::One, limit, || -> Result<()> {
+            panic!("Malformed picture")
+        });
+        assert!(matches!(rendered, Err(Failure::Panic { .. })));
+    }
+}
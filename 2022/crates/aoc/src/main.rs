@@ -0,0 +1,816 @@
+mod all;
+mod answers;
+mod bench;
+mod check;
+mod colour;
+mod guard;
+mod registry;
+mod results;
+mod scaffold;
+
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use answers::AnswerStore;
+use common::*;
+
+const USAGE: &str = "\
+Usage: aoc <year> <day> [--part 1|2|both] [--input <path>] [--time | --bench <runs> [--summary <path>]]
+                        [--format text|json] [--timeout <seconds>] [-v | -vv | -q]
+       aoc [<year> [<day>]] (--record | --check) [--part 1|2|both] [--answers <path>]
+                        [--timeout <seconds>] [-v | -vv | -q]
+       aoc all [<year>] [--part 1|2|both] [--format text|json] [--timeout <seconds>]
+                        [-v | -vv | -q]
+       aoc new <day> <slug>
+       aoc gen <year> <day> [--size <bytes>] [--seed <number>]
+       aoc animate <year> <day> [--part 1|2] [--input <path>] [--fps <number> | --frames <path>]
+                        [--timeout <seconds>]
+       aoc render <year> <day> <path> [--part 1|2] [--input <path>] [--scale <pixels>] [--fps <number>]
+                        [--timeout <seconds>]
+       aoc checkpoint <year> <day> <path> --step <number> [--part 1|2] [--input <path>]
+                        [--timeout <seconds>]
+       aoc resume <path> [--input <path>] [--timeout <seconds>]";
+
+enum Mode {
+    Solve,
+    /// Solve once and report how long each phase took
+    Time,
+    /// Solve many times and report min, median and max for each phase
+    Bench {
+        runs: usize,
+        summary: PathBuf,
+    },
+    /// Save the answers as the accepted ones
+    Record,
+    /// Compare the answers with the accepted ones
+    Check,
+    /// Solve every day in parallel
+    All,
+}
+
+/// Slow enough to follow, fast enough to get somewhere
+const DEFAULT_FPS: f64 = 10.;
+
+/// Big enough to see each cell of a small grid
+const DEFAULT_SCALE: usize = 8;
+
+/// Big enough to be slower than the real input, small enough to be quick
+const DEFAULT_GENERATED_SIZE: usize = 100_000;
+
+/// Long enough for any day, short enough to notice one that never finishes
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    /// One JSON object per part, each on its own line
+    Json,
+}
+
+struct Options {
+    year: Option<u16>,
+    day: Option<u8>,
+    parts: Vec<Part>,
+    input: Option<PathBuf>,
+    answers: PathBuf,
+    mode: Mode,
+    format: Format,
+    /// How long each solver gets before it's abandoned
+    timeout: Option<Duration>,
+    /// Diagnostics below this go unwritten
+    log_level: log::Level,
+}
+
+fn parse_parts(parts: &str) -> Result<Vec<Part>> {
+    match parts {
+        "1" => Ok(vec![Part::One]),
+        "2" => Ok(vec![Part::Two]),
+        "both" => Ok(vec![Part::One, Part::Two]),
+        _ => Err(e!("Invalid --part ({parts}), expected 1, 2 or both")),
+    }
+}
+
+/// `--timeout`'s number of seconds, where 0 means no limit.
+fn parse_timeout(value: Option<&str>) -> Result<Option<Duration>> {
+    let value = value.ok_or_else(|| e!("--timeout must be followed by a number of seconds"))?;
+    let seconds = value
+        .parse::<f64>()
+        .ok()
+        .filter(|seconds| seconds.is_finite() && *seconds >= 0.)
+        .ok_or_else(|| e!("Couldn't parse --timeout seconds ({value})"))?;
+
+    Ok((seconds > 0.).then(|| Duration::from_secs_f64(seconds)))
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options> {
+    let mut positional = vec![];
+    let mut parts = vec![Part::One, Part::Two];
+    let mut input = None;
+    let mut time = false;
+    let mut runs = None;
+    let mut summary = None;
+    let mut record = false;
+    let mut check = false;
+    let mut answers = None;
+    let mut format = Format::Text;
+    let mut timeout = Some(DEFAULT_TIMEOUT);
+    let mut quiet = false;
+    let mut verbose = 0;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| e!("--part must be followed by 1, 2 or both"))?;
+                parts = parse_parts(&value)?;
+            }
+            "--input" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| e!("--input must be followed by a path"))?;
+                input = Some(PathBuf::from(path));
+            }
+            "--time" => time = true,
+            "--bench" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| e!("--bench must be followed by a number of runs"))?;
+                let value = value
+                    .parse::<usize>()
+                    .map_err(|err| e!("Couldn't parse --bench runs ({value}): {err}"))?;
+                if value == 0 {
+                    return Err(e!("--bench needs at least one run"));
+                }
+                runs = Some(value);
+            }
+            "--summary" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| e!("--summary must be followed by a path"))?;
+                summary = Some(PathBuf::from(path));
+            }
+            "--record" => record = true,
+            "--check" => check = true,
+            "--answers" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| e!("--answers must be followed by a path"))?;
+                answers = Some(PathBuf::from(path));
+            }
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    _ => return Err(e!("--format must be followed by text or json")),
+                }
+            }
+            "--timeout" => timeout = parse_timeout(args.next().as_deref())?,
+            "-q" => quiet = true,
+            "-v" => verbose += 1,
+            "-vv" => verbose += 2,
+            flag if flag.starts_with('-') => {
+                return Err(e!("Unrecognised option: {flag}\n{USAGE}"));
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let all = positional.first().is_some_and(|first| first == "all");
+    if all {
+        positional.remove(0);
+    }
+
+    let (year, day) = match &positional[..] {
+        [] => (None, None),
+        [year] => (Some(year), None),
+        [year, day] => (Some(year), Some(day)),
+        _ => return Err(e!("Too many arguments\n{USAGE}")),
+    };
+
+    let year = year
+        .map(|year| {
+            year.parse()
+                .map_err(|err| e!("Couldn't parse year ({year}): {err}"))
+        })
+        .transpose()?;
+    let day = day
+        .map(|day| {
+            day.parse()
+                .map_err(|err| e!("Couldn't parse day ({day}): {err}"))
+        })
+        .transpose()?;
+
+    let modes = [time, runs.is_some(), record, check];
+    if modes.into_iter().filter(|&mode| mode).count() > 1 {
+        return Err(e!(
+            "Only one of --time, --bench, --record and --check can be used at once"
+        ));
+    }
+    if summary.is_some() && runs.is_none() {
+        return Err(e!("--summary only makes sense with --bench"));
+    }
+    if format == Format::Json && (runs.is_some() || record || check) {
+        return Err(e!(
+            "--format json can't be used with --bench, --record or --check"
+        ));
+    }
+    if quiet && verbose > 0 {
+        return Err(e!("-q can't be used with -v or -vv"));
+    }
+    if answers.is_some() && !(record || check) {
+        return Err(e!("--answers only makes sense with --record or --check"));
+    }
+
+    let mode = if all {
+        if day.is_some() {
+            return Err(e!("aoc all takes a year, but not a day\n{USAGE}"));
+        }
+        if time || runs.is_some() || record || check || input.is_some() {
+            return Err(e!(
+                "aoc all can't be used with --time, --bench, --record, --check or --input"
+            ));
+        }
+        Mode::All
+    } else if record {
+        Mode::Record
+    } else if check {
+        Mode::Check
+    } else {
+        let (Some(year), Some(day)) = (year, day) else {
+            return Err(e!("Expected a year and a day\n{USAGE}"));
+        };
+        match runs {
+            Some(runs) => Mode::Bench {
+                runs,
+                summary: summary
+                    .unwrap_or_else(|| PathBuf::from(format!("target/bench/{year}-{day:02}.csv"))),
+            },
+            None if time => Mode::Time,
+            None => Mode::Solve,
+        }
+    };
+
+    Ok(Options {
+        year,
+        day,
+        parts,
+        input,
+        answers: answers.unwrap_or_else(|| PathBuf::from("answers.toml")),
+        mode,
+        format,
+        timeout,
+        log_level: log::Level::from_verbosity(quiet, verbose),
+    })
+}
+
+/// The days picked out by the year and day given, or every registered day.
+fn selected_days(year: Option<u16>, day: Option<u8>) -> Result<Vec<&'static Day>> {
+    let days = registry::DAYS
+        .iter()
+        .filter(|d| year.is_none_or(|year| d.year == year) && day.is_none_or(|day| d.day == day))
+        .collect::<Vec<_>>();
+
+    match (year, day) {
+        (Some(year), Some(day)) if days.is_empty() => {
+            Err(e!("No solver registered for {year} day {day}"))
+        }
+        (Some(year), None) if days.is_empty() => Err(e!("No solvers registered for {year}")),
+        _ => Ok(days),
+    }
+}
+
+/// Inputs for each of `days`. Only a single day can take its input from
+/// `--input` or stdin, so with several days, those without an input file are
+/// given `None`.
+fn selected_inputs(
+    options: &Options,
+    days: Vec<&'static Day>,
+) -> Result<Vec<(&'static Day, Option<String>)>> {
+    if let [day] = days[..] {
+        return Ok(vec![(day, Some(get_input(day, options.input.as_deref())?))]);
+    }
+
+    if options.input.is_some() {
+        return Err(e!("--input can only be used with a single day"));
+    }
+
+    days.into_iter()
+        .map(|day| {
+            let input = input_file(day)
+                .map(|path| {
+                    std::fs::read_to_string(&path).map_err(|err| {
+                        Failure::io(format!("Couldn't read {}", path.display()), err)
+                    })
+                })
+                .transpose()?;
+            Ok((day, input))
+        })
+        .collect()
+}
+
+/// `aoc new <day> <slug>`
+fn new_day(args: &[String]) -> Result<()> {
+    let [day, slug] = args else {
+        return Err(e!("Expected a day and a slug\n{USAGE}"));
+    };
+    let day = day
+        .parse()
+        .map_err(|err| e!("Couldn't parse day ({day}): {err}"))?;
+
+    let dir = scaffold::new_day(day, slug)?;
+    println!(
+        "Created {} and registered it with the runner",
+        dir.display()
+    );
+
+    Ok(())
+}
+
+/// A number of bytes, which can be written like `1e6`.
+fn parse_size(value: &str) -> Result<usize> {
+    value
+        .parse::<usize>()
+        .ok()
+        .or_else(|| {
+            value
+                .parse::<f64>()
+                .ok()
+                .filter(|size| size.is_finite() && *size >= 0.)
+                .map(|size| size as usize)
+        })
+        .ok_or_else(|| e!("Couldn't parse --size ({value}), expected a number of bytes"))
+}
+
+/// The arguments every single-day subcommand shares
+struct DayArgs<'a> {
+    positional: Vec<&'a String>,
+    part: Option<Part>,
+    input: Option<PathBuf>,
+    /// `--timeout`, if it was given
+    timeout: Option<Option<Duration>>,
+}
+
+impl DayArgs<'_> {
+    /// The part asked for, or part one.
+    fn part(&self) -> Part {
+        self.part.unwrap_or(Part::One)
+    }
+
+    /// How long the solver gets before it's abandoned, `default` unless
+    /// `--timeout` said otherwise.
+    fn limit(&self, default: Option<Duration>) -> Option<Duration> {
+        self.timeout.unwrap_or(default)
+    }
+
+    /// Fails if `--part`, `--input` or `--timeout` was given to `command`,
+    /// which has no use for them.
+    fn without_shared_options(self, command: &str) -> Result<Self> {
+        for (flag, given) in [
+            ("--part", self.part.is_some()),
+            ("--input", self.input.is_some()),
+            ("--timeout", self.timeout.is_some()),
+        ] {
+            if given {
+                return Err(e!("aoc {command} doesn't take {flag}\n{USAGE}"));
+            }
+        }
+
+        Ok(self)
+    }
+}
+
+/// Reads a subcommand's `--part`, `--input`, `--timeout` and positional
+/// arguments, handing every other flag to `own` along with the arguments after it.
+/// `own` returns `false` for a flag it doesn't know.
+fn day_args<'a>(
+    args: &'a [String],
+    mut own: impl FnMut(&str, &mut std::slice::Iter<'a, String>) -> Result<bool>,
+) -> Result<DayArgs<'a>> {
+    let mut day_args = DayArgs {
+        positional: vec![],
+        part: None,
+        input: None,
+        timeout: None,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                day_args.part = match args.next().map(String::as_str) {
+                    Some("1") => Some(Part::One),
+                    Some("2") => Some(Part::Two),
+                    _ => return Err(e!("--part must be followed by 1 or 2")),
+                }
+            }
+            "--input" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| e!("--input must be followed by a path"))?;
+                day_args.input = Some(PathBuf::from(path));
+            }
+            "--timeout" => {
+                day_args.timeout = Some(parse_timeout(args.next().map(String::as_str))?);
+            }
+            flag if flag.starts_with('-') => {
+                if !own(flag, &mut args)? {
+                    return Err(e!("Unrecognised option: {flag}\n{USAGE}"));
+                }
+            }
+            _ => day_args.positional.push(arg),
+        }
+    }
+
+    Ok(day_args)
+}
+
+/// `aoc gen <year> <day> [--size <bytes>] [--seed <number>]`
+fn generate(args: &[String]) -> Result<()> {
+    let mut size = DEFAULT_GENERATED_SIZE;
+    let mut seed = 0;
+
+    let args = day_args(args, |flag, args| {
+        match flag {
+            "--size" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| e!("--size must be followed by a number of bytes"))?;
+                size = parse_size(value)?;
+            }
+            "--seed" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| e!("--seed must be followed by a number"))?;
+                seed = value
+                    .parse()
+                    .map_err(|err| e!("Couldn't parse --seed ({value}): {err}"))?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?
+    .without_shared_options("gen")?;
+
+    let day = positional_day(&args.positional)?;
+    let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+    day.generate(seed, size, &mut stdout)
+}
+
+/// The one day picked out by a year and a day.
+fn positional_day(positional: &[&String]) -> Result<&'static Day> {
+    let [year, day] = positional[..] else {
+        return Err(e!("Expected a year and a day\n{USAGE}"));
+    };
+    let year = year
+        .parse()
+        .map_err(|err| e!("Couldn't parse year ({year}): {err}"))?;
+    let day = day
+        .parse()
+        .map_err(|err| e!("Couldn't parse day ({day}): {err}"))?;
+
+    Ok(selected_days(Some(year), Some(day))?[0])
+}
+
+/// `aoc animate <year> <day> [--part 1|2] [--input <path>] [--fps <number> | --frames <path>]`
+fn animate(args: &[String]) -> Result<()> {
+    let mut fps = None;
+    let mut frames = None;
+
+    let args = day_args(args, |flag, args| {
+        match flag {
+            "--fps" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| e!("--fps must be followed by a number of frames"))?;
+                fps = Some(
+                    value
+                        .parse::<f64>()
+                        .map_err(|err| e!("Couldn't parse --fps ({value}): {err}"))?,
+                );
+            }
+            "--frames" => {
+                let path = args
+                    .next()
+                    .ok_or_else(|| e!("--frames must be followed by a path"))?;
+                frames = Some(PathBuf::from(path));
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    let part = args.part();
+
+    let day = positional_day(&args.positional)?;
+    let mut animation = match (&frames, fps) {
+        (Some(_), Some(_)) => return Err(e!("--fps can't be used with --frames")),
+        (Some(path), None) => Animation::to_file(path)?,
+        (None, fps) => Animation::terminal(fps.unwrap_or(DEFAULT_FPS))?,
+    };
+    // Animations are slowed down to be watched, so they aren't timed out
+    // unless asked to be
+    let limit = args.limit(None);
+    let parsed = guard::parse(day, &get_input(day, args.input.as_deref())?, limit)?;
+
+    let (answer, animation) = guard::run(day, part, limit, move || {
+        let answer = day.animate(&parsed, part, &mut animation);
+        Ok((answer, animation))
+    })?;
+    let shown = animation.frames();
+    animation.finish()?;
+
+    if shown == 0 {
+        warn!(
+            "{} day {} has no animation, so it was only solved",
+            day.year, day.day
+        );
+    } else if let Some(path) = frames {
+        info!("Wrote {shown} frames to {}", path.display());
+    }
+    print_answers(&[(part, answer?)]);
+
+    Ok(())
+}
+
+/// `aoc render <year> <day> <path> [--part 1|2] [--input <path>] [--scale <pixels>] [--fps <number>]`
+fn render(args: &[String]) -> Result<()> {
+    let mut scale = DEFAULT_SCALE;
+    let mut fps = DEFAULT_FPS;
+
+    let mut args = day_args(args, |flag, args| {
+        match flag {
+            "--scale" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| e!("--scale must be followed by a number of pixels"))?;
+                scale = value
+                    .parse()
+                    .ok()
+                    .filter(|scale| *scale > 0)
+                    .ok_or_else(|| e!("Couldn't parse --scale ({value})"))?;
+            }
+            "--fps" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| e!("--fps must be followed by a number of frames"))?;
+                fps = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|fps| *fps > 0.)
+                    .ok_or_else(|| e!("Couldn't parse --fps ({value})"))?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+
+    let Some(path) = args.positional.pop() else {
+        return Err(e!("Expected a year, a day and a path\n{USAGE}"));
+    };
+    let path = PathBuf::from(path);
+    let day = positional_day(&args.positional)?;
+    image::Format::from_path(&path)?;
+
+    let (part, limit) = (args.part(), args.limit(Some(DEFAULT_TIMEOUT)));
+    let parsed = guard::parse(day, &get_input(day, args.input.as_deref())?, limit)?;
+    let frames = guard::run(day, part, limit, move || day.render(&parsed, part))
+        .map_err(|err| err.context(format!("Couldn't render {} day {}", day.year, day.day)))?
+        .iter()
+        .map(|frame| frame.scaled(scale))
+        .collect::<Vec<_>>();
+
+    for path in image::save_frames(&frames, fps, &path)? {
+        println!("Saved {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// `aoc checkpoint <year> <day> <path> --step <number> [--part 1|2] [--input <path>]`
+fn checkpoint(args: &[String]) -> Result<()> {
+    let mut step = None;
+
+    let mut args = day_args(args, |flag, args| {
+        match flag {
+            "--step" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| e!("--step must be followed by a number of steps"))?;
+                step = Some(
+                    value
+                        .parse::<usize>()
+                        .map_err(|err| e!("Couldn't parse --step ({value}): {err}"))?,
+                );
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+
+    let Some(path) = args.positional.pop() else {
+        return Err(e!("Expected a year, a day and a path\n{USAGE}"));
+    };
+    let path = PathBuf::from(path);
+    let day = positional_day(&args.positional)?;
+    let step = step.ok_or_else(|| e!("Expected --step, to say where to stop\n{USAGE}"))?;
+
+    let (part, limit) = (args.part(), args.limit(Some(DEFAULT_TIMEOUT)));
+    let parsed = guard::parse(day, &get_input(day, args.input.as_deref())?, limit)?;
+    guard::run(day, part, limit, move || {
+        day.checkpoint(&parsed, part, step)
+    })
+    .map_err(|err| err.context(format!("Couldn't checkpoint {} day {}", day.year, day.day)))?
+    .save(&path)?;
+    println!("Saved {}", path.display());
+
+    Ok(())
+}
+
+/// `aoc resume <path> [--input <path>]`
+fn resume(args: &[String]) -> Result<()> {
+    let args = day_args(args, |_, _| Ok(false))?;
+    if args.part.is_some() {
+        return Err(e!(
+            "aoc resume doesn't take --part, the checkpoint says which part\n{USAGE}"
+        ));
+    }
+
+    let [path] = args.positional[..] else {
+        return Err(e!("Expected the path of a checkpoint\n{USAGE}"));
+    };
+    let checkpoint = checkpoint::Checkpoint::load(&PathBuf::from(path))?;
+    let day = selected_days(Some(checkpoint.year), Some(checkpoint.day))?[0];
+
+    let (part, limit) = (checkpoint.part, args.limit(Some(DEFAULT_TIMEOUT)));
+    let parsed = guard::parse(day, &get_input(day, args.input.as_deref())?, limit)?;
+    let answer = guard::run(day, part, limit, move || day.resume(&parsed, &checkpoint))?;
+    print_answers(&[(part, answer)]);
+
+    Ok(())
+}
+
+fn run() -> Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match args
+        .split_first()
+        .map(|(first, rest)| (first.as_str(), rest))
+    {
+        Some(("new", args)) => return new_day(args),
+        Some(("gen", args)) => return generate(args),
+        Some(("animate", args)) => return animate(args),
+        Some(("render", args)) => return render(args),
+        Some(("checkpoint", args)) => return checkpoint(args),
+        Some(("resume", args)) => return resume(args),
+        _ => {}
+    }
+
+    let options = parse_args(args.into_iter())?;
+    log::set_level(options.log_level);
+    let days = selected_days(options.year, options.day)?;
+
+    match &options.mode {
+        Mode::Record => return record(&options, days),
+        Mode::Check => return check(&options, days),
+        Mode::All => return all(&options, days),
+        _ => {}
+    }
+
+    let day = days[0];
+    let input = get_input(day, options.input.as_deref())?;
+
+    if let Mode::Bench { runs, summary } = &options.mode {
+        let stats = bench::bench(day, &input, &options.parts, *runs, options.timeout)?;
+        bench::print_stats(*runs, &stats);
+        bench::write_summary(summary, &bench::summary(day, *runs, &stats))?;
+        println!("Summary written to {}", summary.display());
+        return Ok(());
+    }
+
+    let attempt = guard::attempt(day, &input, &options.parts, options.timeout);
+
+    if options.format == Format::Json {
+        // Timings are always included, so --time changes nothing here
+        for result in results::to_json(day, &options.parts, &attempt) {
+            println!("{result}");
+        }
+
+        let failures = results::failures(&options.parts, &attempt);
+        if failures > 0 {
+            return Err(e!("{failures} of {} parts failed", options.parts.len()));
+        }
+        return Ok(());
+    }
+
+    let timings = bench::timings(&attempt);
+    print_answers(&attempt.answers()?);
+    if let Mode::Time = options.mode {
+        bench::print_timings(&timings);
+    }
+
+    Ok(())
+}
+
+fn record(options: &Options, days: Vec<&'static Day>) -> Result<()> {
+    let mut store = AnswerStore::load(&options.answers)?;
+    let mut recorded = 0;
+
+    for (day, input) in selected_inputs(options, days)? {
+        let Some(input) = input else {
+            warn!("Skipping {} day {}: no input", day.year, day.day);
+            continue;
+        };
+
+        let answers = match guard::attempt(day, &input, &options.parts, options.timeout).parts {
+            Ok(answers) => answers,
+            Err(err) => {
+                warn!("Skipping {} day {}: {}", day.year, day.day, err.report());
+                continue;
+            }
+        };
+
+        for (part, answer, _) in answers {
+            match answer {
+                Ok(answer) => {
+                    store.insert(day.year, day.day, part, answer);
+                    recorded += 1;
+                }
+                Err(err) => warn!(
+                    "Skipping {} day {} {part}: {}",
+                    day.year,
+                    day.day,
+                    err.report()
+                ),
+            }
+        }
+    }
+
+    store.save(&options.answers)?;
+    println!(
+        "Recorded {recorded} answers to {}",
+        options.answers.display()
+    );
+
+    Ok(())
+}
+
+fn check(options: &Options, days: Vec<&'static Day>) -> Result<()> {
+    let store = AnswerStore::load(&options.answers)?;
+
+    let rows = selected_inputs(options, days)?
+        .into_iter()
+        .flat_map(|(day, input)| {
+            let attempt =
+                input.map(|input| guard::attempt(day, &input, &options.parts, options.timeout));
+            check::check(&store, day, attempt, &options.parts)
+        })
+        .collect::<Vec<_>>();
+
+    check::print_table(&rows);
+
+    let failures = rows.iter().filter(|row| row.status.is_failure()).count();
+    if failures > 0 {
+        return Err(e!("{failures} of {} parts didn't match", rows.len()));
+    }
+
+    Ok(())
+}
+
+fn all(options: &Options, days: Vec<&'static Day>) -> Result<()> {
+    let start = Instant::now();
+    let runs = all::run_all(&days, &options.parts, options.timeout);
+    let wall = start.elapsed();
+
+    match options.format {
+        Format::Text => all::print_table(&runs, wall),
+        Format::Json => {
+            for run in &runs {
+                match &run.outcome {
+                    all::Outcome::Ran(attempt) => {
+                        for result in results::to_json(run.day, &options.parts, attempt) {
+                            println!("{result}");
+                        }
+                    }
+                    all::Outcome::NoInput => {
+                        warn!("Skipping {} day {}: no input", run.day.year, run.day.day)
+                    }
+                }
+            }
+        }
+    }
+
+    let failed = runs.iter().filter(|run| run.failed()).count();
+    if failed > 0 {
+        return Err(e!("{failed} of {} days failed", runs.len()));
+    }
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("{}", err.report());
+        std::process::exit(1);
+    }
+}
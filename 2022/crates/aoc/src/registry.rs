@@ -0,0 +1,18 @@
+use common::Day;
+
+pub const DAYS: &[Day] = &[
+    elf_calories::DAY,
+    rock_paper_scissors::DAY,
+    rucksack_reorganization::DAY,
+    camp_cleanup::DAY,
+    supply_stacks::DAY,
+    tuning_trouble::DAY,
+    no_space_left_on_device::DAY,
+    treetop_tree_house::DAY,
+    rope_bridge::DAY,
+    cathode_ray_tube::DAY,
+    monkey_in_the_middle::DAY,
+    hill_climbing_algorithm::DAY,
+    distress_signal::DAY,
+    regolith_reservoir::DAY,
+];
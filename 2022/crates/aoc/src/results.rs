@@ -0,0 +1,60 @@
+//! Results as JSON Lines, one object per part, for scripts and dashboards.
+
+use common::{json::Json, Day, Part};
+
+use crate::guard::Attempt;
+
+fn result(
+    day: &Day,
+    part: Part,
+    answer: Json,
+    error: Json,
+    parse_ns: u128,
+    solve_ns: Option<u128>,
+) -> Json {
+    Json::object([
+        ("year", day.year.into()),
+        ("day", day.day.into()),
+        ("part", part.into()),
+        ("answer", answer),
+        ("error", error),
+        (
+            "timings",
+            Json::object([("parse_ns", parse_ns.into()), ("solve_ns", solve_ns.into())]),
+        ),
+    ])
+}
+
+/// An object for each of `parts`. If parsing failed, each part carries the
+/// parse failure.
+pub fn to_json(day: &Day, parts: &[Part], attempt: &Attempt) -> Vec<Json> {
+    let parse_ns = attempt.parse_time.as_nanos();
+
+    match &attempt.parts {
+        Ok(results) => results
+            .iter()
+            .map(|(part, answer, time)| {
+                let (answer, error) = match answer {
+                    Ok(answer) => (answer.into(), Json::Null),
+                    Err(err) => (Json::Null, err.into()),
+                };
+                result(day, *part, answer, error, parse_ns, Some(time.as_nanos()))
+            })
+            .collect(),
+        Err(err) => parts
+            .iter()
+            .map(|&part| result(day, part, Json::Null, err.into(), parse_ns, None))
+            .collect(),
+    }
+}
+
+/// How many parts didn't get an answer.
+pub fn failures(parts: &[Part], attempt: &Attempt) -> usize {
+    match &attempt.parts {
+        Ok(results) => results
+            .iter()
+            .filter(|(_, answer, _)| answer.is_err())
+            .count(),
+        Err(_) => parts.len(),
+    }
+}
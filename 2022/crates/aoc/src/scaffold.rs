@@ -0,0 +1,228 @@
+//! `aoc new`, which sets up a crate for a new day and registers it with the
+//! runner.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use common::*;
+
+use crate::registry;
+
+fn crates_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the runner lives in the crates directory")
+        .to_owned()
+}
+
+/// Package names are used as-is, so they must also make a valid crate name
+/// once dashes become underscores.
+fn check_slug(slug: &str) -> Result<()> {
+    let valid = slug.starts_with(|c: char| c.is_ascii_lowercase())
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(e!(
+            "Invalid slug ({slug}), expected lowercase letters, digits, '_' and '-', starting with a letter"
+        ))
+    }
+}
+
+fn manifest(slug: &str) -> String {
+    format!(
+        r#"[package]
+name = "{slug}"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+common = {{ version = "0.1.0", path = "../common" }}
+"#
+    )
+}
+
+fn solver(year: u16, day: u8, slug: &str) -> String {
+    format!(
+        r#"use common::*;
+
+pub const DAY: Day = Day::new::<Puzzle>({year}, {day}, "{slug}", env!("CARGO_MANIFEST_DIR"));
+
+common::example_tests!(DAY);
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {{
+    type Parsed = ();
+
+    fn parse(_input: &str) -> Result<Self::Parsed> {{
+        Ok(())
+    }}
+
+    fn part_one(_parsed: &Self::Parsed) -> Result<Answer> {{
+        Err(e!("Part one isn't solved yet"))
+    }}
+
+    fn part_two(_parsed: &Self::Parsed) -> Result<Answer> {{
+        Err(e!("Part two isn't solved yet"))
+    }}
+}}
+"#
+    )
+}
+
+/// The day's own binary, which just runs it.
+fn binary(slug: &str) -> String {
+    format!(
+        "fn main() -> std::process::ExitCode {{\n    common::run_day(&{}::DAY)\n}}\n",
+        slug.replace('-', "_")
+    )
+}
+
+/// Adds the day as the last of the runner's dependencies.
+fn register_dependency(manifest: &str, day: u8, slug: &str) -> Result<String> {
+    if !manifest.trim_end().ends_with('}') || !manifest.contains("[dependencies]") {
+        return Err(e!(
+            "Expected the runner's Cargo.toml to end with its [dependencies]"
+        ));
+    }
+
+    Ok(format!(
+        "{}\n{slug} = {{ version = \"0.1.0\", path = \"../{day}\" }}\n",
+        manifest.trim_end()
+    ))
+}
+
+/// Adds the day as the last entry of `DAYS`.
+fn register_day(registry: &str, slug: &str) -> Result<String> {
+    let end = registry
+        .find("\n];")
+        .ok_or_else(|| e!("Couldn't find the end of DAYS in registry.rs"))?;
+
+    Ok(format!(
+        "{}\n    {}::DAY,{}",
+        &registry[..end],
+        slug.replace('-', "_"),
+        &registry[end..]
+    ))
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path)
+        .map_err(|err| Failure::io(format!("Couldn't read {}", path.display()), err))
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents)
+        .map_err(|err| Failure::io(format!("Couldn't write {}", path.display()), err))
+}
+
+/// Creates `crates/<day>` and registers it, returning where it was made.
+pub fn new_day(day: u8, slug: &str) -> Result<PathBuf> {
+    check_slug(slug)?;
+    if !(1..=25).contains(&day) {
+        return Err(e!("Invalid day ({day}), expected 1 to 25"));
+    }
+
+    // New days belong to the same year as the ones already here
+    let year = registry::DAYS
+        .iter()
+        .map(|d| d.year)
+        .max()
+        .ok_or_else(|| e!("No days are registered, so the year is unknown"))?;
+
+    if let Some(existing) = registry::DAYS
+        .iter()
+        .find(|d| (d.year == year && d.day == day) || d.name == slug)
+    {
+        return Err(e!(
+            "{year} day {} is already registered as {}",
+            existing.day,
+            existing.name
+        ));
+    }
+
+    let crates = crates_dir();
+    let dir = crates.join(day.to_string());
+    if dir.exists() {
+        return Err(e!("{} already exists", dir.display()));
+    }
+
+    let manifest_path = crates.join("aoc").join("Cargo.toml");
+    let registry_path = crates.join("aoc").join("src").join("registry.rs");
+    let runner_manifest = register_dependency(&read(&manifest_path)?, day, slug)?;
+    let registry = register_day(&read(&registry_path)?, slug)?;
+
+    let src = dir.join("src");
+    let examples = dir.join("examples");
+    for dir in [&src, &examples] {
+        fs::create_dir_all(dir)
+            .map_err(|err| Failure::io(format!("Couldn't create {}", dir.display()), err))?;
+    }
+
+    write(&dir.join("Cargo.toml"), &manifest(slug))?;
+    write(&src.join("lib.rs"), &solver(year, day, slug))?;
+    write(&src.join("main.rs"), &binary(slug))?;
+    write(&dir.join("input"), "")?;
+    write(&examples.join("example.txt"), "")?;
+    write(&examples.join("example.answers"), "Pending\n")?;
+
+    write(&manifest_path, &runner_manifest)?;
+    write(&registry_path, &registry)?;
+
+    // Long slugs push the DAY line past rustfmt's width. Not having rustfmt
+    // installed only leaves it unformatted, so its outcome is ignored.
+    let _ = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .arg(src.join("lib.rs"))
+        .status();
+
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{binary, check_slug, register_day, register_dependency};
+    use common::Result;
+
+    #[test]
+    fn slugs() {
+        assert!(check_slug("proboscidea_volcanium").is_ok());
+        assert!(check_slug("cathode-ray_tube").is_ok());
+        assert!(check_slug("").is_err());
+        assert!(check_slug("15").is_err());
+        assert!(check_slug("Monkey Map").is_err());
+    }
+
+    #[test]
+    fn registration() -> Result<()> {
+        let manifest = "[dependencies]\ncommon = { version = \"0.1.0\", path = \"../common\" }\n";
+        assert_eq!(
+            register_dependency(manifest, 15, "beacon-exclusion_zone")?,
+            "[dependencies]\ncommon = { version = \"0.1.0\", path = \"../common\" }\nbeacon-exclusion_zone = { version = \"0.1.0\", path = \"../15\" }\n"
+        );
+
+        let registry = "pub const DAYS: &[Day] = &[\n    regolith_reservoir::DAY,\n];\n";
+        assert_eq!(
+            register_day(registry, "beacon-exclusion_zone")?,
+            "pub const DAYS: &[Day] = &[\n    regolith_reservoir::DAY,\n    beacon_exclusion_zone::DAY,\n];\n"
+        );
+
+        assert!(register_day("", "beacon_exclusion_zone").is_err());
+
+        assert_eq!(
+            binary("beacon-exclusion_zone"),
+            "fn main() -> std::process::ExitCode {\n    common::run_day(&beacon_exclusion_zone::DAY)\n}\n"
+        );
+
+        Ok(())
+    }
+}
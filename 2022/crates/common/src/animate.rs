@@ -0,0 +1,350 @@
+//! Watching simulations run. An [`Animation`] is handed a frame after each
+//! step of a simulation, and either draws it in the terminal at a steady
+//! rate or dumps it to a file as fast as it comes.
+//!
+//! In the terminal, space pauses and resumes, `n` steps one frame at a time
+//! while paused, `+` and `-` change the speed, and `q` stops drawing so the
+//! simulation can run to the end. Ctrl-C puts the terminal back and quits.
+
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{BufWriter, Read, Write},
+    path::Path,
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use crate::{e, Failure, Result};
+
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const HOME: &str = "\x1b[H";
+const CLEAR_LINE: &str = "\x1b[K";
+const CLEAR_BELOW: &str = "\x1b[J";
+
+/// What ctrl-C sends once it's no longer a signal
+const CTRL_C: u8 = 0x03;
+
+/// The slowest and fastest `+` and `-` can go
+const FPS_RANGE: (f64, f64) = (0.25, 1000.);
+
+/// Runs `stty` on `tty`, giving what it printed.
+fn stty(tty: &File, args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(tty.try_clone().ok()?)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+/// The controlling terminal, switched to raw mode so that keys arrive as
+/// they're pressed. Its settings are put back when it's dropped.
+struct Keys {
+    tty: File,
+    saved: String,
+}
+
+impl Keys {
+    /// `None` when there's no terminal to read from, or no `stty` to
+    /// configure it.
+    fn open() -> Option<Self> {
+        let tty = File::open("/dev/tty").ok()?;
+        let saved = stty(&tty, &["-g"])?;
+        // Reads return straight away, whether or not a key was pressed.
+        // Ctrl-C arrives as a key rather than a signal, so that the terminal
+        // can be put back before quitting.
+        stty(
+            &tty,
+            &["-icanon", "-echo", "-isig", "min", "0", "time", "0"],
+        )?;
+
+        Some(Self { tty, saved })
+    }
+
+    fn pressed(&mut self) -> Option<u8> {
+        let mut key = [0];
+        match self.tty.read(&mut key) {
+            Ok(1) => Some(key[0]),
+            _ => None,
+        }
+    }
+}
+
+impl Drop for Keys {
+    fn drop(&mut self) {
+        stty(&self.tty, &[&self.saved]);
+    }
+}
+
+/// Drawing frames in place on the terminal.
+struct Terminal {
+    keys: Option<Keys>,
+    fps: f64,
+    paused: bool,
+    /// When the next frame is due
+    due: Instant,
+}
+
+impl Terminal {
+    fn status(&self, frame: usize) -> String {
+        let state = match self.paused {
+            true => "paused, n to step".to_owned(),
+            false => format!("{} fps", self.fps),
+        };
+        let keys = match self.keys {
+            Some(_) => " | space pause | +/- speed | q skip to the end",
+            None => "",
+        };
+
+        format!("Frame {frame} | {state}{keys}")
+    }
+
+    /// Waits until the next frame is due, handling any keys pressed in the
+    /// meantime. Returns `false` once `q` is pressed.
+    fn wait(&mut self, out: &mut dyn Write, frame: usize) -> std::io::Result<bool> {
+        loop {
+            let mut step = false;
+            while let Some(key) = self.keys.as_mut().and_then(Keys::pressed) {
+                match key {
+                    b' ' => self.paused = !self.paused,
+                    b'n' if self.paused => step = true,
+                    b'+' => self.fps = (self.fps * 2.).min(FPS_RANGE.1),
+                    b'-' => self.fps = (self.fps / 2.).max(FPS_RANGE.0),
+                    b'q' => {
+                        // Nothing reads keys once drawing stops, so ctrl-C
+                        // goes back to being a signal
+                        self.keys = None;
+                        return Ok(false);
+                    }
+                    CTRL_C => self.interrupt(),
+                    _ => continue,
+                }
+                write!(out, "\r{}{CLEAR_LINE}", self.status(frame))?;
+                out.flush()?;
+            }
+
+            let now = Instant::now();
+            if step || (!self.paused && now >= self.due) {
+                self.due = self.due.max(now) + self.delay();
+                return Ok(true);
+            }
+
+            let wait = match self.paused {
+                true => Duration::from_millis(20),
+                false => self.due - now,
+            };
+            std::thread::sleep(wait.min(Duration::from_millis(20)));
+        }
+    }
+
+    /// Puts the terminal back and quits, the way ctrl-C would have.
+    fn interrupt(&mut self) -> ! {
+        self.keys = None;
+        println!("{SHOW_CURSOR}");
+        let _ = std::io::stdout().flush();
+        std::process::exit(130)
+    }
+
+    fn delay(&self) -> Duration {
+        Duration::from_secs_f64(1. / self.fps)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        println!("{SHOW_CURSOR}");
+        let _ = std::io::stdout().flush();
+    }
+}
+
+/// Where a simulation's frames go.
+pub struct Animation {
+    out: Box<dyn Write + Send>,
+    /// `None` when frames are dumped rather than drawn
+    terminal: Option<Terminal>,
+    frames: usize,
+    stopped: bool,
+    /// The first write that failed, which stops the animation
+    error: Option<std::io::Error>,
+}
+
+impl Animation {
+    /// Draws frames in the terminal, `fps` of them a second.
+    pub fn terminal(fps: f64) -> Result<Self> {
+        if !(FPS_RANGE.0..=FPS_RANGE.1).contains(&fps) {
+            return Err(e!(
+                "Invalid frame rate ({fps}), expected {} to {}",
+                FPS_RANGE.0,
+                FPS_RANGE.1
+            ));
+        }
+
+        let mut out = std::io::stdout();
+        write!(out, "{HIDE_CURSOR}{CLEAR_SCREEN}")
+            .and_then(|()| out.flush())
+            .map_err(|err| Failure::io("Couldn't set up the terminal", err))?;
+
+        Ok(Self {
+            out: Box::new(out),
+            terminal: Some(Terminal {
+                keys: Keys::open(),
+                fps,
+                paused: false,
+                due: Instant::now(),
+            }),
+            frames: 0,
+            stopped: false,
+            error: None,
+        })
+    }
+
+    /// Writes every frame to `out`, one after another, without waiting.
+    pub fn headless(out: impl Write + Send + 'static) -> Self {
+        Self {
+            out: Box::new(out),
+            terminal: None,
+            frames: 0,
+            stopped: false,
+            error: None,
+        }
+    }
+
+    /// Writes every frame to the file at `path`.
+    pub fn to_file(path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .map_err(|err| Failure::io(format!("Couldn't create {}", path.display()), err))?;
+
+        Ok(Self::headless(BufWriter::new(file)))
+    }
+
+    /// Whether frames are still wanted. Simulations can check this to avoid
+    /// putting together frames that won't be shown.
+    pub fn drawing(&self) -> bool {
+        !self.stopped
+    }
+
+    /// How many frames have been shown.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Shows the next frame, waiting until it's due. Once drawing has stopped,
+    /// because `q` was pressed or a write failed, frames are ignored.
+    pub fn frame(&mut self, title: impl Display, frame: impl Display) {
+        if self.stopped {
+            return;
+        }
+
+        self.frames += 1;
+        if let Err(err) = self.show(&title.to_string(), &frame.to_string()) {
+            self.stopped = true;
+            self.error = Some(err);
+        }
+    }
+
+    fn show(&mut self, title: &str, frame: &str) -> std::io::Result<()> {
+        let Some(terminal) = &mut self.terminal else {
+            writeln!(self.out, "== Frame {}: {title} ==", self.frames)?;
+            return writeln!(self.out, "{}", frame.trim_end_matches('\n'));
+        };
+
+        write!(self.out, "{HOME}")?;
+        for line in title.lines().chain(frame.lines()) {
+            writeln!(self.out, "{line}{CLEAR_LINE}")?;
+        }
+        write!(self.out, "{CLEAR_BELOW}{}", terminal.status(self.frames))?;
+        self.out.flush()?;
+
+        if !terminal.wait(&mut self.out, self.frames)? {
+            self.stopped = true;
+        }
+
+        Ok(())
+    }
+
+    /// Puts the terminal back, or flushes the file, reporting whether every
+    /// frame could be written.
+    pub fn finish(mut self) -> Result<()> {
+        let flushed = self.out.flush();
+        // Restores the terminal before anything else is printed
+        self.terminal = None;
+
+        match self.error.take().map_or(flushed, Err) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(Failure::io("Couldn't write the animation", err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::Write,
+        sync::{Arc, Mutex},
+    };
+
+    use super::Animation;
+
+    /// A buffer that can still be read after the animation takes it
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn headless() {
+        let out = Shared::default();
+        let mut animation = Animation::headless(out.clone());
+
+        animation.frame("Start", "#.\n.#\n");
+        animation.frame(format_args!("Step {}", 1), ".#\n#.");
+        assert!(animation.drawing());
+        assert_eq!(animation.frames(), 2);
+        animation.finish().unwrap();
+
+        let out = String::from_utf8(out.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(
+            out,
+            "== Frame 1: Start ==\n#.\n.#\n== Frame 2: Step 1 ==\n.#\n#.\n"
+        );
+    }
+
+    #[test]
+    fn stops_when_writing_fails() {
+        struct Broken;
+
+        impl Write for Broken {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::BrokenPipe.into())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut animation = Animation::headless(Broken);
+        animation.frame("Start", "#");
+        assert!(!animation.drawing());
+        animation.frame("Ignored", "#");
+        assert_eq!(animation.frames(), 1);
+
+        assert!(animation.finish().is_err());
+    }
+}
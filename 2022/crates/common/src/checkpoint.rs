@@ -0,0 +1,313 @@
+//! Stopping a simulation part way through, to look at its state or to carry
+//! on from there later.
+//!
+//! A checkpoint is plain text: a header saying which puzzle it came from and
+//! how many steps had been taken, then a blank line and the day's own state.
+//! Every line is a `key: value` pair, written in the same order every time,
+//! so checkpoints from two runs can be compared with `diff`.
+
+use std::{fmt::Display, fs, path::Path};
+
+use crate::{Answer, Failure, Part, Result, Scanner};
+
+/// A simulation's state that can be saved in a checkpoint and read back.
+pub trait State: Sized {
+    /// `key: value` lines, one for each thing that can change as the
+    /// simulation runs.
+    fn save(&self) -> String;
+
+    /// Reads back what [`State::save`] wrote.
+    fn restore(s: Scanner) -> Result<Self>;
+}
+
+/// A checkpoint's step and state, to carry on from
+pub type Saved<'a> = (usize, &'a str);
+
+/// How far a simulation got.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Progress {
+    /// It reached the step it was asked to stop at, and this was its state
+    Stopped(String),
+    /// It ran to the end first
+    Finished(Answer),
+}
+
+/// Runs a simulation one step at a time, from the start or from a saved
+/// `(step, state)`, stopping once `until` steps have been taken.
+///
+/// `step` takes the step numbered by its second argument, counting from 0,
+/// and returns `false` when there are no steps left.
+pub fn run<S: State>(
+    from: Option<Saved>,
+    start: impl FnOnce() -> S,
+    until: Option<usize>,
+    step: impl FnMut(&mut S, usize) -> Result<bool>,
+    answer: impl FnOnce(&S) -> Answer,
+) -> Result<Progress> {
+    let (taken, mut state) = match from {
+        Some((taken, saved)) => (taken, S::restore(Scanner::new(saved))?),
+        None => (0, start()),
+    };
+
+    if advance(&mut state, taken, until, step)? {
+        Ok(Progress::Stopped(state.save()))
+    } else {
+        Ok(Progress::Finished(answer(&state)))
+    }
+}
+
+/// Runs a simulation until it has no steps left, the way [`run`] does when
+/// it isn't asked to stop, counting steps from 0.
+pub fn finish<S>(state: &mut S, step: impl FnMut(&mut S, usize) -> Result<bool>) -> Result<()> {
+    advance(state, 0, None, step)?;
+    Ok(())
+}
+
+/// Takes steps from `taken` on, returning `true` if it reached `until`
+/// before running out of steps.
+fn advance<S>(
+    state: &mut S,
+    mut taken: usize,
+    until: Option<usize>,
+    mut step: impl FnMut(&mut S, usize) -> Result<bool>,
+) -> Result<bool> {
+    loop {
+        if until == Some(taken) {
+            return Ok(true);
+        }
+        if !step(state, taken)? {
+            return Ok(false);
+        }
+        taken += 1;
+    }
+}
+
+/// A list of actions that each take some number of steps, like moves of
+/// several squares, so a simulation can find what step N is doing without
+/// listing every step. Cheapest when steps are looked up in order.
+pub struct Schedule<'a, T> {
+    actions: &'a [T],
+    length: fn(&T) -> usize,
+    /// The action the last step looked up was part of
+    index: usize,
+    /// The first step of that action
+    start: usize,
+}
+
+impl<'a, T> Schedule<'a, T> {
+    /// `actions`, each taking `length` steps.
+    pub fn new(actions: &'a [T], length: fn(&T) -> usize) -> Self {
+        Self {
+            actions,
+            length,
+            index: 0,
+            start: 0,
+        }
+    }
+
+    /// How many actions there are.
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// The index of the action step `step` is part of, the action, and how
+    /// many of its steps come before this one. `None` once every action
+    /// is over.
+    pub fn at(&mut self, step: usize) -> Option<(usize, &'a T, usize)> {
+        if step < self.start {
+            self.index = 0;
+            self.start = 0;
+        }
+        while let Some(action) = self.actions.get(self.index) {
+            let length = (self.length)(action);
+            if step < self.start + length {
+                return Some((self.index, action, step - self.start));
+            }
+            self.start += length;
+            self.index += 1;
+        }
+
+        None
+    }
+}
+
+/// A saved simulation, and the puzzle it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    /// How many steps had been taken
+    pub step: usize,
+    /// The day's own `key: value` lines
+    pub state: String,
+}
+
+impl Display for Checkpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let part = match self.part {
+            Part::One => 1,
+            Part::Two => 2,
+        };
+
+        writeln!(f, "Year: {}", self.year)?;
+        writeln!(f, "Day: {}", self.day)?;
+        writeln!(f, "Part: {part}")?;
+        writeln!(f, "Step: {}", self.step)?;
+        writeln!(f)?;
+        write!(f, "{}", self.state)
+    }
+}
+
+impl Checkpoint {
+    /// Reads a checkpoint written by its [`Display`].
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut s = Scanner::new(text);
+        let header = s.until("\n\n")?;
+        let mut lines = header.lines();
+        let mut field = |key: &str| {
+            lines
+                .next()
+                .ok_or_else(|| header.error_at_end(format!("Expected a '{key}' line")))?
+                .field(key)
+        };
+
+        let year = field("Year")?.parse_all(Scanner::int)?;
+        let day = field("Day")?.parse_all(Scanner::int)?;
+        let part = field("Part")?.parse_all(|s| {
+            s.spanned(|s| match s.int()? {
+                1 => Ok(Part::One),
+                2 => Ok(Part::Two),
+                part => Err(Failure::validation(format!("There's no part {part}"))),
+            })
+        })?;
+        let step = field("Step")?.parse_all(Scanner::int)?;
+
+        Ok(Self {
+            year,
+            day,
+            part,
+            step,
+            state: s.rest().to_owned(),
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string())
+            .map_err(|err| Failure::io(format!("Couldn't write {}", path.display()), err))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| Failure::io(format!("Couldn't read {}", path.display()), err))?;
+
+        Self::parse(&text).map_err(|err| err.context(format!("In {}", path.display())))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{run, Checkpoint, Progress, Saved, Schedule, State};
+    use crate::{Part, Result, Scanner};
+
+    /// A counter that counts to ten
+    struct Count(u32);
+
+    impl State for Count {
+        fn save(&self) -> String {
+            format!("Count: {}\n", self.0)
+        }
+
+        fn restore(s: Scanner) -> Result<Self> {
+            s.parse_all(|s| {
+                let line = s.until("\n")?;
+                let count = line.parse_all(|s| s.field("Count")?.parse_all(Scanner::int))?;
+                Ok(Self(count))
+            })
+        }
+    }
+
+    fn count(from: Option<Saved>, until: Option<usize>) -> Result<Progress> {
+        run(
+            from,
+            || Count(0),
+            until,
+            |count, step| {
+                if step == 10 {
+                    return Ok(false);
+                }
+                count.0 += 1;
+                Ok(true)
+            },
+            |count| count.0.into(),
+        )
+    }
+
+    #[test]
+    fn stops_and_resumes() -> Result<()> {
+        let Progress::Stopped(state) = count(None, Some(4))? else {
+            panic!("Counting finished before step 4");
+        };
+        assert_eq!(state, "Count: 4\n");
+
+        assert_eq!(
+            count(Some((4, &state)), None)?,
+            count(None, None)?,
+            "resuming should end the same way as not stopping"
+        );
+        assert_eq!(count(None, Some(20))?, Progress::Finished(10.into()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn schedules() {
+        let lengths = [2, 0, 1, 3];
+        let mut schedule = Schedule::new(&lengths, |length| *length);
+
+        let steps = (0..7)
+            .map(|step| schedule.at(step).map(|(index, _, within)| (index, within)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            steps,
+            [
+                Some((0, 0)),
+                Some((0, 1)),
+                Some((2, 0)),
+                Some((3, 0)),
+                Some((3, 1)),
+                Some((3, 2)),
+                None
+            ]
+        );
+        assert_eq!(
+            schedule.at(1).map(|(index, _, within)| (index, within)),
+            Some((0, 1))
+        );
+    }
+
+    #[test]
+    fn round_trips() -> Result<()> {
+        let checkpoint = Checkpoint {
+            year: 2022,
+            day: 11,
+            part: Part::Two,
+            step: 5000,
+            state: "Relief: divide by three\n".to_owned(),
+        };
+        let text = checkpoint.to_string();
+
+        assert_eq!(
+            text,
+            "Year: 2022\nDay: 11\nPart: 2\nStep: 5000\n\nRelief: divide by three\n"
+        );
+        assert_eq!(Checkpoint::parse(&text)?, checkpoint);
+        assert!(Checkpoint::parse("Year: 2022\nDay: 11\nPart: 3\nStep: 0\n\n").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,372 @@
+use std::{error::Error, fmt::Display, ops::Range, time::Duration};
+
+use crate::Part;
+
+/// Where in the puzzle input something went wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// 1-based line number
+    pub line: usize,
+    /// Byte range of the offending text within the whole input
+    pub span: Range<usize>,
+    /// The full line the span starts on
+    pub source_line: String,
+    /// Byte offset of the span within `source_line`
+    column: usize,
+}
+
+impl Location {
+    /// Locates `span` (byte offsets into `input`) by line and column.
+    pub fn in_input(input: &str, span: Range<usize>) -> Self {
+        let start = span.start.min(input.len());
+        let line_start = input[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[start..].find('\n').map_or(input.len(), |i| start + i);
+
+        Self {
+            line: input[..line_start].matches('\n').count() + 1,
+            span,
+            source_line: input[line_start..line_end].to_owned(),
+            column: start - line_start,
+        }
+    }
+
+    /// Locates `slice`, which must have been borrowed from `input`.
+    ///
+    /// Falls back to the very start of `input` if it wasn't.
+    pub fn of_slice(input: &str, slice: &str) -> Self {
+        let start = (slice.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        let span = if start <= input.len() && start + slice.len() <= input.len() {
+            start..start + slice.len()
+        } else {
+            0..0
+        };
+
+        Self::in_input(input, span)
+    }
+
+    /// 1-based column, counted in characters.
+    pub fn column(&self) -> usize {
+        self.source_line[..self.column].chars().count() + 1
+    }
+
+    /// The offending text, cut short at the end of its line.
+    pub fn text(&self) -> &str {
+        let end = (self.column + self.span.len()).min(self.source_line.len());
+        &self.source_line[self.column..end]
+    }
+
+    /// Renders the line with the span underlined by carets.
+    pub fn snippet(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let indent = self.source_line[..self.column]
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        let carets = "^".repeat(self.text().chars().count().max(1));
+
+        format!(
+            "{gutter}--> line {}, column {}\n{gutter} |\n{number} | {}\n{gutter} | {indent}{carets}",
+            self.line,
+            self.column(),
+            self.source_line,
+        )
+    }
+}
+
+/// Which of a day's solvers was running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub year: u16,
+    pub day: u8,
+    /// `None` while parsing
+    pub part: Option<Part>,
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.part {
+            Some(part) => write!(f, "{} day {} {part}", self.year, self.day),
+            None => write!(f, "{} day {} parsing", self.year, self.day),
+        }
+    }
+}
+
+/// Everything that can go wrong while loading, parsing or solving a puzzle.
+#[derive(Debug)]
+pub enum Failure {
+    /// The input isn't in the expected format
+    Parse {
+        message: String,
+        location: Option<Location>,
+    },
+    /// The input is well-formed but breaks one of the puzzle's rules
+    Validation {
+        message: String,
+        location: Option<Location>,
+    },
+    /// A simulation reached a state it can't continue from
+    Simulation { message: String },
+    Io {
+        message: String,
+        source: std::io::Error,
+    },
+    /// A solver panicked, and the panic was caught
+    Panic { message: String, step: Step },
+    /// A solver ran past its time limit and was abandoned
+    Timeout {
+        message: String,
+        step: Step,
+        limit: Duration,
+    },
+    /// A failure with more context layered on top
+    Context {
+        message: String,
+        source: Box<Failure>,
+    },
+    /// Anything built with the `e!` macro
+    Other(String),
+}
+
+impl Failure {
+    pub fn from(value: String) -> Self {
+        Self::Other(value)
+    }
+
+    pub fn parse(message: impl Into<String>) -> Self {
+        Self::Parse {
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::Validation {
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    pub fn simulation(message: impl Into<String>) -> Self {
+        Self::Simulation {
+            message: message.into(),
+        }
+    }
+
+    pub fn io(message: impl Into<String>, source: std::io::Error) -> Self {
+        Self::Io {
+            message: message.into(),
+            source,
+        }
+    }
+
+    pub fn panic(step: Step, message: impl Into<String>) -> Self {
+        Self::Panic {
+            message: message.into(),
+            step,
+        }
+    }
+
+    pub fn timeout(step: Step, limit: Duration) -> Self {
+        Self::Timeout {
+            message: format!("Didn't finish within {limit:?}"),
+            step,
+            limit,
+        }
+    }
+
+    /// Attaches `location` to a parse or validation failure. Other kinds of
+    /// failure are returned unchanged.
+    pub fn at(mut self, new_location: Location) -> Self {
+        if let Self::Parse { location, .. } | Self::Validation { location, .. } = &mut self {
+            *location = Some(new_location);
+        }
+        self
+    }
+
+    /// Moves a location found within a single line onto line `number` of
+    /// the whole input, which starts `offset` bytes into it.
+    pub(crate) fn on_line(mut self, number: usize, offset: usize) -> Self {
+        let mut cause = &mut self;
+        while let Self::Context { source, .. } = cause {
+            cause = source;
+        }
+        if let Self::Parse {
+            location: Some(location),
+            ..
+        }
+        | Self::Validation {
+            location: Some(location),
+            ..
+        } = cause
+        {
+            location.line = number;
+            location.span = location.span.start + offset..location.span.end + offset;
+        }
+        self
+    }
+
+    /// Wraps this failure in another that explains what was being attempted.
+    pub fn context(self, message: impl Into<String>) -> Self {
+        Self::Context {
+            message: message.into(),
+            source: Box::new(self),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Parse { message, .. }
+            | Self::Validation { message, .. }
+            | Self::Simulation { message }
+            | Self::Panic { message, .. }
+            | Self::Timeout { message, .. }
+            | Self::Io { message, .. }
+            | Self::Context { message, .. }
+            | Self::Other(message) => message,
+        }
+    }
+
+    /// A short name for the variant, for machine-readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Parse { .. } => "parse",
+            Self::Validation { .. } => "validation",
+            Self::Simulation { .. } => "simulation",
+            Self::Panic { .. } => "panic",
+            Self::Timeout { .. } => "timeout",
+            Self::Io { .. } => "io",
+            Self::Context { .. } => "context",
+            Self::Other(_) => "other",
+        }
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Self::Parse { location, .. } | Self::Validation { location, .. } => location.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Renders this failure, its snippet and everything that caused it.
+    pub fn report(&self) -> String {
+        let mut report = self.to_string();
+        let mut source = self.source();
+
+        while let Some(cause) = source {
+            report.push_str(&format!("\ncaused by: {cause}"));
+            source = cause.source();
+        }
+
+        report
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse { message, .. } => write!(f, "Parse error: {message}")?,
+            Self::Validation { message, .. } => write!(f, "Invalid input: {message}")?,
+            Self::Simulation { message } => write!(f, "Simulation error: {message}")?,
+            Self::Panic { message, step } => write!(f, "{step} panicked: {message}")?,
+            Self::Timeout { message, step, .. } => write!(f, "{step}: {message}")?,
+            Self::Io { message, .. } | Self::Context { message, .. } | Self::Other(message) => {
+                write!(f, "{message}")?
+            }
+        }
+
+        if let Some(location) = self.location() {
+            write!(f, "\n{}", location.snippet())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for Failure {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Context { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{Failure, Location, Step};
+    use crate::Part;
+
+    const INPUT: &str = "move 1 from 2 to 1\nmove x from 1 to 3\nmove 2 from 2 to 1";
+
+    #[test]
+    fn location_in_input() {
+        let location = Location::in_input(INPUT, 24..25);
+
+        assert_eq!(location.line, 2);
+        assert_eq!(location.column(), 6);
+        assert_eq!(location.text(), "x");
+        assert_eq!(location.source_line, "move x from 1 to 3");
+    }
+
+    #[test]
+    fn location_of_slice() {
+        let line = INPUT.split('\n').nth(2).expect("INPUT has three lines");
+
+        let location = Location::of_slice(INPUT, &line[5..6]);
+
+        assert_eq!(location.line, 3);
+        assert_eq!(location.span, 43..44);
+        assert_eq!(location.text(), "2");
+
+        let location = Location::of_slice(INPUT, "elsewhere");
+
+        assert_eq!(location.line, 1);
+        assert_eq!(location.span, 0..0);
+    }
+
+    #[test]
+    fn snippet() {
+        let failure = Failure::parse("Expected a number").at(Location::in_input(INPUT, 24..25));
+
+        assert_eq!(
+            failure.to_string(),
+            "Parse error: Expected a number
+ --> line 2, column 6
+  |
+2 | move x from 1 to 3
+  |      ^"
+        );
+    }
+
+    #[test]
+    fn report() {
+        let failure = Failure::simulation("Stack 4 doesn't exist").context("Couldn't move crates");
+
+        assert_eq!(
+            failure.report(),
+            "Couldn't move crates\ncaused by: Simulation error: Stack 4 doesn't exist"
+        );
+    }
+
+    #[test]
+    fn aborted() {
+        let step = Step {
+            year: 2022,
+            day: 11,
+            part: Some(Part::Two),
+        };
+
+        assert_eq!(
+            Failure::timeout(step, Duration::from_secs(5)).to_string(),
+            "2022 day 11 Part two: Didn't finish within 5s"
+        );
+        assert_eq!(
+            Failure::panic(Step { part: None, ..step }, "explicit panic").to_string(),
+            "2022 day 11 parsing panicked: explicit panic"
+        );
+    }
+}
@@ -0,0 +1,365 @@
+//! Regression cases kept next to each day's crate. Every `examples/<case>.txt`
+//! is an input, and `examples/<case>.answers` holds what it should produce, in
+//! the same format the runner prints:
+//!
+//! ```text
+//! Part one: 24000
+//! Part two:
+//! multi-line
+//! answers
+//! ```
+//!
+//! A part can be left out while it's unsolved. A case with no answers at all
+//! fails, unless its `.answers` file just says `Pending`.
+
+use std::{fs, path::Path};
+
+use crate::{checkpoint::Checkpoint, e, Animation, Day, Failure, Output, Part, Result, Rng};
+
+/// One input and the answers it should produce
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub expected: Vec<(Part, String)>,
+    /// Its `.answers` file says `Pending`, so it's only parsed
+    pub pending: bool,
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path)
+        .map_err(|err| Failure::io(format!("Couldn't read {}", path.display()), err))
+}
+
+/// Parses the contents of an `.answers` file.
+pub fn parse_answers(text: &str) -> Result<Vec<(Part, String)>> {
+    let mut answers: Vec<(Part, String)> = vec![];
+
+    for line in crate::lines(&crate::normalize(text)) {
+        let header = [(Part::One, "Part one:"), (Part::Two, "Part two:")]
+            .into_iter()
+            .find_map(|(part, header)| Some((part, line.strip_prefix(header)?)));
+
+        match (header, answers.last_mut()) {
+            (Some((part, answer)), _) => {
+                if answers.iter().any(|(p, _)| *p == part) {
+                    return Err(Failure::validation(format!("{part} is listed twice")));
+                }
+                answers.push((part, answer.trim().to_owned()));
+            }
+            (None, Some((_, answer))) => {
+                if !answer.is_empty() {
+                    answer.push('\n');
+                }
+                answer.push_str(line);
+            }
+            (None, None) => {
+                return Err(Failure::parse(format!(
+                    "Expected a \"Part one:\" or \"Part two:\" line, found {line:?}"
+                )))
+            }
+        }
+    }
+
+    Ok(answers)
+}
+
+/// Every case in `dir`, sorted by name.
+pub fn load_examples(dir: &Path) -> Result<Vec<Example>> {
+    let entries = fs::read_dir(dir)
+        .map_err(|err| Failure::io(format!("Couldn't read {}", dir.display()), err))?;
+
+    let mut inputs = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| Failure::io(format!("Couldn't read {}", dir.display()), err))?
+        .into_iter()
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect::<Vec<_>>();
+    inputs.sort();
+
+    inputs
+        .into_iter()
+        .map(|input_path| {
+            let answers_path = input_path.with_extension("answers");
+            let answers = read(&answers_path)?;
+            let pending = answers.trim() == "Pending";
+            let expected = match pending {
+                true => vec![],
+                false => parse_answers(&answers)
+                    .map_err(|err| err.context(format!("In {}", answers_path.display())))?,
+            };
+
+            Ok(Example {
+                name: input_path
+                    .file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned(),
+                input: read(&input_path)?,
+                expected,
+                pending,
+            })
+        })
+        .collect()
+}
+
+/// Runs `example` and describes each way it didn't match.
+pub fn check_example(day: &Day, example: &Example) -> Vec<String> {
+    if example.expected.is_empty() && !example.pending {
+        return vec![format!(
+            "{}: no answers to check, write \"Pending\" in its .answers file while it's unsolved",
+            example.name
+        )];
+    }
+
+    let parsed = match day.parse(&example.input) {
+        Ok(parsed) => parsed,
+        Err(err) => return vec![format!("{}: {}", example.name, err.report())],
+    };
+
+    example
+        .expected
+        .iter()
+        .filter_map(|(part, expected)| match day.part(*part, &parsed) {
+            Ok(answer) if answer.to_string() == *expected => None,
+            Ok(answer) => Some(format!(
+                "{}: {part}: expected {expected:?}, got {:?}",
+                example.name,
+                answer.to_string()
+            )),
+            Err(err) => Some(format!("{}: {part}: {}", example.name, err.report())),
+        })
+        .collect()
+}
+
+/// Checks every case in `day`'s `examples` directory.
+pub fn check_examples(day: &Day) -> Result<()> {
+    let dir = Path::new(day.crate_dir).join("examples");
+    let examples = load_examples(&dir)?;
+
+    if examples.is_empty() {
+        return Err(e!("No examples in {}", dir.display()));
+    }
+
+    let mut mismatches = vec![];
+    for example in &examples {
+        let failed = check_example(day, example);
+        // Shown by the test harness, so every case is accounted for
+        let status = match (failed.is_empty(), example.pending) {
+            (false, _) => "FAILED",
+            (true, true) => "pending",
+            (true, false) => "ok",
+        };
+        println!("{} example {}: {status}", day.name, example.name);
+        mismatches.extend(failed);
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(e!(
+            "{} of {}'s examples failed:\n{}",
+            mismatches.len(),
+            day.name,
+            mismatches.join("\n")
+        ))
+    }
+}
+
+/// Checks that animating each of `day`'s examples gives the same answers as
+/// solving them.
+pub fn check_animated(day: &Day) -> Result<()> {
+    for example in load_examples(&Path::new(day.crate_dir).join("examples"))? {
+        let parsed = day.parse(&example.input)?;
+        for (part, expected) in &example.expected {
+            let mut animation = Animation::headless(std::io::sink());
+            let answer = day
+                .animate(&parsed, *part, &mut animation)
+                .map_err(|err| err.context(format!("{}: {part}", example.name)))?;
+            animation.finish()?;
+
+            if answer.to_string() != *expected {
+                return Err(e!(
+                    "{}: {part}: expected {expected:?} when animated, got {:?}",
+                    example.name,
+                    answer.to_string()
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that each of `day`'s examples gets the expected answers when
+/// checkpointed part way through, saved, read back and resumed. Only days
+/// that are simulations can be checked.
+pub fn check_resumed(day: &Day) -> Result<()> {
+    for example in load_examples(&Path::new(day.crate_dir).join("examples"))? {
+        let parsed = day.parse(&example.input)?;
+        for (part, expected) in &example.expected {
+            for step in [0, 1, 3] {
+                let context = || format!("{}: {part} from step {step}", example.name);
+                let checkpoint = day
+                    .checkpoint(&parsed, *part, step)
+                    .map_err(|err| err.context(context()))?;
+
+                let saved = checkpoint.to_string();
+                if Checkpoint::parse(&saved)? != checkpoint {
+                    return Err(e!("{}: the checkpoint changed when read back", context()));
+                }
+
+                let answer = day
+                    .resume(&parsed, &checkpoint)
+                    .map_err(|err| err.context(context()))?;
+                if answer.to_string() != *expected {
+                    return Err(e!(
+                        "{}: expected {expected:?} when resumed, got {:?}",
+                        context(),
+                        answer.to_string()
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that a few of `day`'s generated inputs parse, and that each seed
+/// always gives the same input. Days without a generator pass.
+pub fn check_generated(day: &Day) -> Result<()> {
+    let generate = |seed| {
+        let mut input = vec![];
+        match (day.generate)(&mut Rng::new(seed), &mut Output::new(&mut input, 2000)) {
+            Ok(()) => Ok(Some(String::from_utf8_lossy(&input).into_owned())),
+            Err(err) if err.kind() == std::io::ErrorKind::Unsupported => Ok(None),
+            Err(err) => Err(Failure::io("Couldn't generate an input", err)),
+        }
+    };
+
+    for seed in 0..4 {
+        let Some(input) = generate(seed)? else {
+            return Ok(());
+        };
+        if generate(seed)?.as_ref() != Some(&input) {
+            return Err(e!("Seed {seed} gave two different inputs"));
+        }
+        day.parse(&input)
+            .map_err(|err| err.context(format!("In the input generated from seed {seed}")))?;
+    }
+
+    Ok(())
+}
+
+/// Adds tests that check every case in the day's `examples` directory, both
+/// solved and animated, that the day's generated inputs parse, and that its
+/// parser never panics.
+#[macro_export]
+macro_rules! example_tests {
+    ($day:expr) => {
+        #[cfg(test)]
+        mod examples {
+            use super::*;
+
+            #[test]
+            fn examples() {
+                if let Err(err) = $crate::check_examples(&$day) {
+                    panic!("{}", err.report());
+                }
+            }
+
+            #[test]
+            fn generated() {
+                if let Err(err) = $crate::examples::check_generated(&$day) {
+                    panic!("{}", err.report());
+                }
+            }
+
+            #[test]
+            fn animated() {
+                if let Err(err) = $crate::examples::check_animated(&$day) {
+                    panic!("{}", err.report());
+                }
+            }
+
+            #[test]
+            fn fuzz() {
+                if let Err(err) = $crate::fuzz::check_day(&$day) {
+                    panic!("{}", err.report());
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_example, parse_answers, Example};
+    use crate::{Answer, Day, Part, Result, Solution};
+
+    struct Length;
+
+    impl Solution for Length {
+        type Parsed = usize;
+
+        fn parse(input: &str) -> Result<Self::Parsed> {
+            Ok(input.len())
+        }
+
+        fn part_one(length: &Self::Parsed) -> Result<Answer> {
+            Ok((*length).into())
+        }
+
+        fn part_two(length: &Self::Parsed) -> Result<Answer> {
+            Ok((*length).into())
+        }
+    }
+
+    const LENGTH: Day = Day::new::<Length>(2022, 99, "length", env!("CARGO_MANIFEST_DIR"));
+
+    #[test]
+    fn answers() -> Result<()> {
+        let answers = parse_answers("Part one: 13140\nPart two:\n##..\n.##.\n")?;
+
+        assert_eq!(
+            answers,
+            [
+                (Part::One, "13140".to_owned()),
+                (Part::Two, "##..\n.##.".to_owned())
+            ]
+        );
+
+        assert!(parse_answers("")?.is_empty());
+
+        assert!(parse_answers("Part one: 1\nPart one: 2").is_err());
+        assert!(parse_answers("24000").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn cases_need_answers_unless_pending() {
+        let example = |expected: &[(Part, &str)], pending| Example {
+            name: "case".to_owned(),
+            input: "abc".to_owned(),
+            expected: expected
+                .iter()
+                .map(|(part, answer)| (*part, answer.to_string()))
+                .collect(),
+            pending,
+        };
+
+        assert!(check_example(&LENGTH, &example(&[(Part::One, "3")], false)).is_empty());
+        assert_eq!(
+            check_example(&LENGTH, &example(&[(Part::Two, "4")], false)).len(),
+            1
+        );
+        assert!(check_example(&LENGTH, &example(&[], true)).is_empty());
+        let unchecked = check_example(&LENGTH, &example(&[], false));
+        assert!(
+            unchecked[0].contains("no answers to check"),
+            "{unchecked:?}"
+        );
+    }
+}
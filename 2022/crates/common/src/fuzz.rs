@@ -0,0 +1,294 @@
+//! Property checks for parsers. Whatever bytes they're given, parsers should
+//! turn bad input away with a [`Failure`] rather than panic, and anything
+//! that can be rendered back to text should render what it parsed.
+//!
+//! Inputs are made by mutating known good ones. A panic is minimised and
+//! saved as `fuzz/<parser>/<hash>.txt` next to the crate, and every saved
+//! case is replayed before any new ones are tried.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{catch_panic, e, examples, Day, Failure, Output, Result, Rng};
+
+/// How many inputs each parser is tried on, unless `AOC_FUZZ_CASES` says
+/// otherwise. Enough to catch the obvious, quick enough for every test run.
+const DEFAULT_CASES: usize = 300;
+
+fn cases() -> usize {
+    std::env::var("AOC_FUZZ_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(DEFAULT_CASES)
+}
+
+/// Bytes that tend to mean something to a parser
+const INTERESTING: &[u8] = b"0123456789-+,[]$ \n:.abczAEIOSXYZ";
+
+fn random_byte(rng: &mut Rng) -> u8 {
+    match rng.chance(3, 4) {
+        true => *rng.pick(INTERESTING),
+        false => rng.below(256) as u8,
+    }
+}
+
+/// A few random changes to one of `seeds`, or random bytes now and then.
+/// Whatever isn't UTF-8 is replaced, since inputs are read as text.
+pub fn mutate(rng: &mut Rng, seeds: &[String]) -> String {
+    if seeds.is_empty() || rng.chance(1, 8) {
+        let length = rng.below(64);
+        let bytes = (0..length).map(|_| random_byte(rng)).collect::<Vec<_>>();
+        return String::from_utf8_lossy(&bytes).into_owned();
+    }
+
+    let mut bytes = rng.pick(seeds).as_bytes().to_vec();
+    for _ in 0..rng.between(1, 4) {
+        let at = rng.below(bytes.len() + 1);
+        let until = (at + rng.between(1, 8) as usize).min(bytes.len());
+        match rng.below(6) {
+            0 if at < bytes.len() => bytes[at] = random_byte(rng),
+            1 => bytes.insert(at, random_byte(rng)),
+            2 => drop(bytes.drain(at..until)),
+            3 => {
+                let copy = bytes[at..until].to_vec();
+                bytes.splice(at..at, copy);
+            }
+            4 => {
+                let other = rng.pick(seeds).as_bytes();
+                let from = rng.below(other.len() + 1);
+                let to = (from + rng.below(32)).min(other.len());
+                bytes.splice(at..until, other[from..to].iter().copied());
+            }
+            _ => bytes.truncate(at),
+        }
+    }
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Removes as much of `input` as it can while it still `fails`, so that
+/// what's left is mostly the cause.
+pub fn minimise(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut chars = input.chars().collect::<Vec<_>>();
+    let mut chunk = chars.len().div_ceil(2);
+
+    while chunk > 0 {
+        let mut start = 0;
+        let mut removed = false;
+        while start < chars.len() {
+            let end = (start + chunk).min(chars.len());
+            let candidate = [&chars[..start], &chars[end..]].concat();
+            if fails(&candidate.iter().collect::<String>()) {
+                chars = candidate;
+                removed = true;
+            } else {
+                start += chunk;
+            }
+        }
+        if !removed {
+            chunk /= 2;
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+/// FNV-1a, which names saved cases after what's in them.
+fn hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// The saved cases in `dir`, sorted by name.
+fn saved(dir: &Path) -> Result<Vec<(PathBuf, String)>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(vec![]);
+    };
+
+    let mut paths = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let input = fs::read_to_string(&path)
+                .map_err(|err| Failure::io(format!("Couldn't read {}", path.display()), err))?;
+            Ok((path, input))
+        })
+        .collect()
+}
+
+/// Where crashes in `parser`, from the crate in `crate_dir`, are saved.
+pub fn dir(crate_dir: &str, parser: &str) -> PathBuf {
+    Path::new(crate_dir).join("fuzz").join(parser)
+}
+
+/// Minimises `input`, which made `check` panic, and saves it to `dir`.
+fn save_crash(dir: &Path, input: &str, check: &impl Fn(&str) -> Result<()>) -> Failure {
+    let crashes = |input: &str| catch_panic(|| check(input)).is_err();
+    let input = minimise(input, crashes);
+    let message = catch_panic(|| check(&input)).err().unwrap_or_default();
+
+    let path = dir.join(format!("{:016x}.txt", hash(&input)));
+    let saved = fs::create_dir_all(dir).and_then(|()| fs::write(&path, &input));
+    let saved = match saved {
+        Ok(()) => format!("Saved to {}", path.display()),
+        Err(err) => format!("Couldn't save it to {}: {err}", path.display()),
+    };
+
+    e!("Panicked on {input:?}: {message}\n{saved}")
+}
+
+/// Replays the cases saved in `dir`, then tries `check` on inputs mutated
+/// from `seeds`. `check` fails when a property doesn't hold, and shouldn't
+/// ever panic.
+pub fn check(dir: &Path, seeds: &[String], check: impl Fn(&str) -> Result<()>) -> Result<()> {
+    let run = |input: &str| match catch_panic(|| check(input)) {
+        Ok(result) => result.map_err(|err| err.context(format!("With input {input:?}"))),
+        Err(_) => Err(save_crash(dir, input, &check)),
+    };
+
+    for (path, input) in saved(dir)? {
+        run(&input).map_err(|err| err.context(format!("In {}", path.display())))?;
+    }
+
+    let mut rng = Rng::new(hash(&dir.to_string_lossy()));
+    for _ in 0..cases() {
+        run(&mutate(&mut rng, seeds))?;
+    }
+
+    Ok(())
+}
+
+/// Checks that `parse` never panics. Its failures are fine.
+pub fn check_parser<T>(
+    dir: &Path,
+    seeds: &[String],
+    parse: impl Fn(&str) -> Result<T>,
+) -> Result<()> {
+    check(dir, seeds, |input| {
+        let _ = parse(input);
+        Ok(())
+    })
+}
+
+/// Checks that `parse` never panics, that rendering what it parsed gives
+/// back each of `seeds`, and that whatever it parsed from anything else
+/// renders to something that parses to the same rendering.
+pub fn check_round_trip<T>(
+    dir: &Path,
+    seeds: &[String],
+    parse: impl Fn(&str) -> Result<T>,
+    render: impl Fn(&T) -> String,
+) -> Result<()> {
+    for seed in seeds {
+        let rendered = render(&parse(seed)?);
+        if rendered.trim_end() != seed.trim_end() {
+            return Err(e!("{seed:?} rendered as {rendered:?}"));
+        }
+    }
+
+    check(dir, seeds, |input| {
+        let Ok(parsed) = parse(input) else {
+            return Ok(());
+        };
+        let rendered = render(&parsed);
+        let again = parse(&rendered)
+            .map_err(|err| err.context(format!("Couldn't parse the rendering {rendered:?}")))?;
+
+        match render(&again) {
+            rerendered if rerendered == rendered => Ok(()),
+            rerendered => Err(e!(
+                "{rendered:?} rendered as {rerendered:?} the second time"
+            )),
+        }
+    })
+}
+
+/// Inputs to start from for `day`: its examples and a few generated inputs.
+pub fn day_seeds(day: &Day) -> Result<Vec<String>> {
+    let mut seeds = examples::load_examples(&Path::new(day.crate_dir).join("examples"))?
+        .into_iter()
+        .map(|example| example.input)
+        .collect::<Vec<_>>();
+
+    for seed in 0..2 {
+        let mut input = vec![];
+        if (day.generate)(&mut Rng::new(seed), &mut Output::new(&mut input, 500)).is_ok() {
+            seeds.push(String::from_utf8_lossy(&input).into_owned());
+        }
+    }
+
+    Ok(seeds)
+}
+
+/// Checks that `day`'s parser never panics, saving crashes in the crate's
+/// `fuzz/parse` directory.
+pub fn check_day(day: &Day) -> Result<()> {
+    check_parser(&dir(day.crate_dir, "parse"), &day_seeds(day)?, |input| {
+        day.parse(input)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::{check_parser, minimise, mutate};
+    use crate::{Failure, Rng};
+
+    #[test]
+    fn minimises() {
+        let input = "move 1 from 2 to 3\nmove 10 from 0 to 1\n";
+        let fails = |input: &str| input.contains("from 0");
+
+        assert_eq!(minimise(input, fails), "from 0");
+        assert_eq!(minimise("", fails), "");
+    }
+
+    #[test]
+    fn mutates_deterministically() {
+        let seeds = ["1-2,3-4\n".to_owned(), "5-6,7-8\n".to_owned()];
+        let inputs = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..50)
+                .map(|_| mutate(&mut rng, &seeds))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(inputs(1), inputs(1));
+        assert!(inputs(1).iter().any(|input| !seeds.contains(input)));
+    }
+
+    #[test]
+    fn saves_minimised_crashes() {
+        let dir = std::env::temp_dir().join(format!("aoc-fuzz-{}", std::process::id()));
+        let seeds = ["1 2 3\n4 5 6\n".to_owned()];
+        let parse = |input: &str| match input.contains('9') {
+            true => panic!("Nine!"),
+            false => Ok::<_, Failure>(()),
+        };
+
+        let failure = check_parser(&dir, &seeds, parse).unwrap_err();
+        assert!(failure
+            .message()
+            .starts_with("Panicked on \"9\": Nine! (at "));
+
+        let saved = fs::read_dir(&dir).unwrap().collect::<Vec<_>>();
+        assert_eq!(saved.len(), 1);
+        let path = saved[0].as_ref().unwrap().path();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "9");
+
+        // Saved cases are replayed first
+        let failure = check_parser(&dir, &[], parse).unwrap_err();
+        assert!(failure.report().contains(&path.display().to_string()));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
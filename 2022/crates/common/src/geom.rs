@@ -0,0 +1,339 @@
+//! Points and directions on a 2D grid. `y` grows downwards, so that
+//! [`Direction::Up`] takes you towards the first line of a puzzle input.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::Position;
+
+/// A point that can go anywhere, including below zero.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Clone, Copy)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A point that can't go below zero, for indexing into grids.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Clone, Copy)]
+pub struct UPoint {
+    pub x: usize,
+    pub y: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// The smallest rectangle containing some points, edges included.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Each coordinate reduced to -1, 0 or 1.
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Whether `other` is this point or one of the eight around it.
+    pub fn touches(self, other: Point) -> bool {
+        self.chebyshev(other) <= 1
+    }
+
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.delta()
+    }
+
+    /// The four points above, right of, below and left of this one.
+    pub fn neighbours(self) -> [Point; 4] {
+        Direction::ALL.map(|direction| self.step(direction))
+    }
+
+    /// Like [`Point::neighbours`], but with the diagonals too.
+    pub fn neighbours_with_diagonals(self) -> [Point; 8] {
+        [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ]
+        .map(|(x, y)| self + Point::new(x, y))
+    }
+
+    /// `None` if either coordinate is negative.
+    pub fn to_unsigned(self) -> Option<UPoint> {
+        Some(UPoint::new(
+            usize::try_from(self.x).ok()?,
+            usize::try_from(self.y).ok()?,
+        ))
+    }
+}
+
+impl UPoint {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: UPoint) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(self, other: UPoint) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// `None` if the step would go below zero.
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        self.signed().step(direction).to_unsigned()
+    }
+
+    /// The up to four points above, right of, below and left of this one.
+    pub fn neighbours(self) -> impl Iterator<Item = UPoint> {
+        self.signed()
+            .neighbours()
+            .into_iter()
+            .filter_map(Point::to_unsigned)
+    }
+
+    /// Like [`UPoint::neighbours`], but with the diagonals too.
+    pub fn neighbours_with_diagonals(self) -> impl Iterator<Item = UPoint> {
+        self.signed()
+            .neighbours_with_diagonals()
+            .into_iter()
+            .filter_map(Point::to_unsigned)
+    }
+
+    pub fn signed(self) -> Point {
+        Point::new(self.x as i64, self.y as i64)
+    }
+}
+
+impl Direction {
+    /// Clockwise, starting from `Up`
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    /// The other three directions, in `ALL` order.
+    pub fn others(self) -> impl Iterator<Item = Direction> {
+        Direction::ALL.into_iter().filter(move |d| *d != self)
+    }
+}
+
+impl Neg for Direction {
+    type Output = Direction;
+
+    fn neg(self) -> Self::Output {
+        self.turn_right().turn_right()
+    }
+}
+
+impl Bounds {
+    pub fn new(point: Point) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// `None` if there aren't any points.
+    pub fn of(points: impl IntoIterator<Item = Point>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let mut bounds = Self::new(points.next()?);
+        for point in points {
+            bounds.include(point);
+        }
+        Some(bounds)
+    }
+
+    /// Grows these bounds just enough to contain `point`.
+    pub fn include(&mut self, point: Point) {
+        self.min = Point::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn width(&self) -> u64 {
+        self.min.x.abs_diff(self.max.x) + 1
+    }
+
+    pub fn height(&self) -> u64 {
+        self.min.y.abs_diff(self.max.y) + 1
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl From<UPoint> for Point {
+    fn from(point: UPoint) -> Self {
+        point.signed()
+    }
+}
+
+impl From<Position> for UPoint {
+    fn from((x, y): Position) -> Self {
+        UPoint::new(x, y)
+    }
+}
+
+impl From<UPoint> for Position {
+    fn from(point: UPoint) -> Self {
+        (point.x, point.y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Bounds, Direction, Point, UPoint};
+
+    #[test]
+    fn distances() {
+        let a = Point::new(-2, 3);
+        let b = Point::new(1, -1);
+
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert!(!a.touches(b));
+        assert!(b.touches(b + Point::new(1, 1)));
+        assert_eq!((b - a).signum(), Point::new(1, -1));
+    }
+
+    #[test]
+    fn neighbourhoods() {
+        assert_eq!(
+            Point::ORIGIN.neighbours(),
+            [
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0)
+            ]
+        );
+        assert_eq!(UPoint::new(0, 0).neighbours().count(), 2);
+        assert_eq!(UPoint::new(0, 1).neighbours_with_diagonals().count(), 5);
+        assert_eq!(UPoint::new(0, 0).step(Direction::Left), None);
+        assert_eq!(
+            UPoint::new(0, 0).step(Direction::Down),
+            Some(UPoint::new(0, 1))
+        );
+    }
+
+    #[test]
+    fn turning() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(-Direction::Down, Direction::Up);
+        assert_eq!(
+            Direction::Right.others().collect::<Vec<_>>(),
+            [Direction::Up, Direction::Down, Direction::Left]
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        let bounds = Bounds::of([Point::new(498, 4), Point::new(503, 9), Point::new(494, 6)])
+            .expect("there are some points");
+
+        assert_eq!(bounds.min, Point::new(494, 4));
+        assert_eq!(bounds.max, Point::new(503, 9));
+        assert_eq!((bounds.width(), bounds.height()), (10, 6));
+        assert!(bounds.contains(Point::new(500, 5)));
+        assert!(!bounds.contains(Point::new(500, 0)));
+        assert_eq!(Bounds::of([]), None);
+    }
+}
@@ -0,0 +1,403 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{geom::UPoint, Failure, Location, Result, Scanner};
+
+/// `(x, y)`, with `(0, 0)` in the top left corner
+pub type Position = (usize, usize);
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Position) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut cell)
+            .collect();
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Fails unless every row is as long as the first.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            return Err(Failure::validation(format!(
+                "Grid is not rectangular: row {} has {} cells but row 1 has {width}",
+                y + 1,
+                rows[y].len()
+            )));
+        }
+
+        Ok(Self {
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+            width,
+        })
+    }
+
+    /// Parses a map with one cell per character and one row per line.
+    /// Failures from `cell` are located at the offending character.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for mut line in Scanner::new(input).lines() {
+            let text = line.rest();
+            let mut row_width = 0;
+
+            while !line.is_empty() {
+                cells.push(line.spanned(|s| cell(s.char()?))?);
+                row_width += 1;
+            }
+
+            let width = *width.get_or_insert(row_width);
+            if row_width != width {
+                return Err(Failure::validation(format!(
+                    "Grid is not rectangular: row {} has {row_width} cells but row 1 has {width}",
+                    height + 1
+                ))
+                .at(Location::of_slice(input, text)));
+            }
+
+            height += 1;
+        }
+
+        Ok(Self {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: impl Into<Position>) -> bool {
+        let (x, y) = position.into();
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: impl Into<Position>) -> Option<&T> {
+        let (x, y) = position.into();
+        self.contains((x, y))
+            .then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, position: impl Into<Position>) -> Option<&mut T> {
+        let (x, y) = position.into();
+        self.contains((x, y))
+            .then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// Like [`Grid::get`], but for positions that might be negative.
+    pub fn get_signed(&self, (x, y): (isize, isize)) -> Option<&T> {
+        self.get((usize::try_from(x).ok()?, usize::try_from(y).ok()?))
+    }
+
+    /// Wraps `position` around the edges, as if the grid were tiled forever.
+    ///
+    /// Panics if the grid is empty.
+    pub fn get_wrapping(&self, (x, y): (isize, isize)) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        // `chunks_mut` panics on a chunk size of 0
+        self.cells.chunks_mut(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        (0..self.height).map(move |y| &self[(x, y)])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The first position, row by row, whose cell matches `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Position> {
+        self.enumerate()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// The up to four positions above, right of, below and left of `position`.
+    pub fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> {
+        let (width, height) = (self.width, self.height);
+        UPoint::from(position)
+            .neighbours()
+            .filter(move |p| p.x < width && p.y < height)
+            .map(Position::from)
+    }
+
+    /// Like [`Grid::neighbours`], but with the diagonals too.
+    pub fn neighbours_with_diagonals(&self, position: Position) -> impl Iterator<Item = Position> {
+        let (width, height) = (self.width, self.height);
+        UPoint::from(position)
+            .neighbours_with_diagonals()
+            .filter(move |p| p.x < width && p.y < height)
+            .map(Position::from)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Renders each cell with `cell` instead of its own `Display`.
+    pub fn display_with<'a, D: Display>(
+        &'a self,
+        cell: impl Fn(&T) -> D + 'a,
+    ) -> impl Display + 'a {
+        DisplayWith { grid: self, cell }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn fill(&mut self, value: T) {
+        self.cells.fill(value);
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    /// Rotates a quarter turn counter-clockwise, so the last column becomes
+    /// the first row.
+    pub fn rotate_left(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(x, y)| {
+            self[(self.width - 1 - y, x)].clone()
+        })
+    }
+
+    /// Rotates a quarter turn clockwise, so the first column becomes the
+    /// first row.
+    pub fn rotate_right(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(x, y)| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "{position:?} is outside a {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{position:?} is outside a {width}x{height} grid"))
+    }
+}
+
+impl<T> Index<UPoint> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: UPoint) -> &Self::Output {
+        &self[Position::from(position)]
+    }
+}
+
+impl<T> IndexMut<UPoint> for Grid<T> {
+    fn index_mut(&mut self, position: UPoint) -> &mut Self::Output {
+        &mut self[Position::from(position)]
+    }
+}
+
+struct DisplayWith<'a, T, F> {
+    grid: &'a Grid<T>,
+    cell: F,
+}
+
+impl<T> Grid<T> {
+    fn fmt_with(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        cell: impl Fn(&mut std::fmt::Formatter<'_>, &T) -> std::fmt::Result,
+    ) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for value in row {
+                cell(f, value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, D: Display, F: Fn(&T) -> D> Display for DisplayWith<'_, T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.grid
+            .fmt_with(f, |f, value| write!(f, "{}", (self.cell)(value)))
+    }
+}
+
+/// One line per row, without a trailing newline
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with(f, |f, value| write!(f, "{value}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Grid;
+    use crate::{Failure, Result};
+
+    fn digits(input: &str) -> Result<Grid<u32>> {
+        Grid::parse(input, |c| {
+            c.to_digit(10)
+                .ok_or_else(|| Failure::parse(format!("Non-digit in input: {c:?}")))
+        })
+    }
+
+    #[test]
+    fn parse_and_display() -> Result<()> {
+        let grid = digits("123\n456")?;
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(
+            grid.display_with(|n| if n % 2 == 0 { '#' } else { '.' })
+                .to_string(),
+            ".#.\n#.#"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_failures_are_located() {
+        let failure = digits("123\n4x6").expect_err("x isn't a digit");
+        let location = failure.location().expect("cell failures have a location");
+        assert_eq!((location.line, location.column()), (2, 2));
+
+        let failure = digits("123\n45\n789").expect_err("row 2 is short");
+        assert!(matches!(failure, Failure::Validation { .. }));
+        assert_eq!(failure.location().map(|l| l.line), Some(2));
+    }
+
+    #[test]
+    fn indexing() -> Result<()> {
+        let grid = digits("123\n456")?;
+
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get_signed((-1, 0)), None);
+        assert_eq!(grid.get_signed((1, 1)), Some(&5));
+        assert_eq!(grid.get_wrapping((-1, 0)), &3);
+        assert_eq!(grid.get_wrapping((4, 3)), &5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rows_columns_and_neighbours() -> Result<()> {
+        let grid = digits("123\n456\n789")?;
+
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [3, 6, 9]);
+        assert_eq!(
+            grid.columns().map(|c| c.sum::<u32>()).collect::<Vec<_>>(),
+            [12, 15, 18]
+        );
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours_with_diagonals((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours_with_diagonals((2, 2)).count(), 3);
+        assert_eq!(grid.position(|n| *n == 8), Some((1, 2)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn transpose_and_rotate() -> Result<()> {
+        let grid = digits("123\n456")?;
+
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        assert_eq!(grid.rotate_left().to_string(), "36\n25\n14");
+        assert_eq!(grid.rotate_right().to_string(), "41\n52\n63");
+        assert_eq!(grid.rotate_left().rotate_right(), grid);
+        assert_eq!(
+            grid.rotate_left().rotate_left().rotate_left().rotate_left(),
+            grid
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,510 @@
+//! Pictures of puzzle states, for write-ups. A [`Grid`] becomes an [`Image`]
+//! through a [`Palette`], and an image, or a sequence of them, can be saved
+//! as PPM, PNG or SVG. Everything is encoded here, so nothing needs to be
+//! installed.
+
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{e, Failure, Grid, Result};
+
+/// A colour, 8 bits a channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Colour {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Colour {
+    pub const BLACK: Colour = Colour::rgb(0, 0, 0);
+    pub const WHITE: Colour = Colour::rgb(255, 255, 255);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Part way from this colour to `other`, with `t` from 0 to 1.
+    pub fn mix(self, other: Colour, t: f64) -> Colour {
+        let t = t.clamp(0., 1.);
+        let channel =
+            |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * t).round() as u8;
+
+        Colour::rgb(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+        )
+    }
+}
+
+/// `#rrggbb`, as SVG and CSS write it
+impl Display for Colour {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// How the values in a grid are coloured in.
+pub trait Palette<T> {
+    fn colour(&self, value: &T) -> Colour;
+}
+
+/// Any function from a value to a colour is a palette.
+impl<T, F: Fn(&T) -> Colour> Palette<T> for F {
+    fn colour(&self, value: &T) -> Colour {
+        self(value)
+    }
+}
+
+/// Colours numbers from `min` to `max` along evenly spaced `stops`. Numbers
+/// outside the range get the colour at its nearest end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    pub stops: Vec<Colour>,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Gradient {
+    /// Dark blue through red to yellow, for heatmaps.
+    pub fn heat(min: f64, max: f64) -> Self {
+        Self {
+            stops: vec![
+                Colour::rgb(16, 16, 64),
+                Colour::rgb(200, 32, 32),
+                Colour::rgb(255, 230, 80),
+            ],
+            min,
+            max,
+        }
+    }
+
+    /// Black to white.
+    pub fn grey(min: f64, max: f64) -> Self {
+        Self {
+            stops: vec![Colour::BLACK, Colour::WHITE],
+            min,
+            max,
+        }
+    }
+
+    pub fn at(&self, number: f64) -> Colour {
+        let Some((&last, _)) = self.stops.split_last() else {
+            return Colour::BLACK;
+        };
+        if self.stops.len() == 1 || self.max <= self.min {
+            return last;
+        }
+
+        let t = ((number - self.min) / (self.max - self.min)).clamp(0., 1.);
+        let position = t * (self.stops.len() - 1) as f64;
+        let stop = (position as usize).min(self.stops.len() - 2);
+
+        self.stops[stop].mix(self.stops[stop + 1], position - stop as f64)
+    }
+}
+
+impl<T: Copy + Into<f64>> Palette<T> for Gradient {
+    fn colour(&self, value: &T) -> Colour {
+        self.at((*value).into())
+    }
+}
+
+/// The file formats an [`Image`] can be saved as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Binary PPM (`P6`), the simplest there is
+    Ppm,
+    /// Uncompressed PNG
+    Png,
+    /// One square per pixel, with runs of a colour merged
+    Svg,
+}
+
+impl Format {
+    /// The format a path's extension asks for.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ppm") => Ok(Format::Ppm),
+            Some("png") => Ok(Format::Png),
+            Some("svg") => Ok(Format::Svg),
+            _ => Err(e!(
+                "Can't tell the format of {}, expected .ppm, .png or .svg",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// A picture, row by row from the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Colour>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, colour: Colour) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![colour; width * height],
+        }
+    }
+
+    /// One pixel for each cell of `grid`, coloured by `palette`.
+    pub fn from_grid<T>(grid: &Grid<T>, palette: &impl Palette<T>) -> Self {
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.iter().map(|value| palette.colour(value)).collect(),
+        }
+    }
+
+    /// One pixel for each cell of a grid that's already been coloured in.
+    pub fn from_colours(grid: &Grid<Colour>) -> Self {
+        Self::from_grid(grid, &|colour: &Colour| *colour)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Colour> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// Colours the pixel at `(x, y)`, if it's in the image.
+    pub fn set(&mut self, x: usize, y: usize, colour: Colour) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = colour;
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[Colour]> {
+        self.pixels.chunks(self.width.max(1)).take(self.height)
+    }
+
+    /// Each pixel blown up to a `factor` by `factor` square, so small grids
+    /// don't come out blurry when they're viewed.
+    pub fn scaled(&self, factor: usize) -> Self {
+        let mut pixels = Vec::with_capacity(self.pixels.len() * factor * factor);
+        for row in self.rows() {
+            let row = row
+                .iter()
+                .flat_map(|&colour| std::iter::repeat_n(colour, factor))
+                .collect::<Vec<_>>();
+            for _ in 0..factor {
+                pixels.extend_from_slice(&row);
+            }
+        }
+
+        Self {
+            width: self.width * factor,
+            height: self.height * factor,
+            pixels,
+        }
+    }
+
+    pub fn write(&self, format: Format, out: &mut dyn Write) -> std::io::Result<()> {
+        match format {
+            Format::Ppm => self.write_ppm(out),
+            Format::Png => self.write_png(out),
+            Format::Svg => write_svg(&[self], 0., out),
+        }
+    }
+
+    pub fn write_ppm(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        let bytes = self
+            .pixels
+            .iter()
+            .flat_map(|colour| [colour.r, colour.g, colour.b])
+            .collect::<Vec<_>>();
+        out.write_all(&bytes)
+    }
+
+    pub fn write_png(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        // Every row starts with its filter type, which is always none
+        let mut raw = Vec::with_capacity(self.height * (1 + 3 * self.width));
+        for row in self.rows() {
+            raw.push(0);
+            raw.extend(row.iter().flat_map(|colour| [colour.r, colour.g, colour.b]));
+        }
+
+        let mut header = vec![];
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8 bits a channel, RGB, deflate, adaptive filtering, no interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+        write_chunk(out, b"IHDR", &header)?;
+        write_chunk(out, b"IDAT", &zlib_stored(&raw))?;
+        write_chunk(out, b"IEND", &[])
+    }
+
+    /// Saves the image in the format `path`'s extension asks for.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let format = Format::from_path(path)?;
+        write_file(path, |out| self.write(format, out))
+    }
+}
+
+fn write_file(
+    path: &Path,
+    write: impl FnOnce(&mut dyn Write) -> std::io::Result<()>,
+) -> Result<()> {
+    let mut out = File::create(path)
+        .map(BufWriter::new)
+        .map_err(|err| Failure::io(format!("Couldn't create {}", path.display()), err))?;
+
+    write(&mut out)
+        .and_then(|()| out.flush())
+        .map_err(|err| Failure::io(format!("Couldn't write {}", path.display()), err))
+}
+
+fn write_chunk(out: &mut dyn Write, kind: &[u8; 4], data: &[u8]) -> std::io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    out.write_all(&crc32(&[kind, data]).to_be_bytes())
+}
+
+/// The CRC that ends every PNG chunk, over each of `parts` in turn.
+fn crc32(parts: &[&[u8]]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in parts.iter().flat_map(|part| part.iter()) {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => 0xedb8_8320 ^ (crc >> 1),
+                _ => crc >> 1,
+            };
+        }
+    }
+    !crc
+}
+
+/// The checksum that ends a zlib stream.
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+/// `data` as a zlib stream of stored deflate blocks, which PNG readers
+/// accept without anything having been compressed.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const BLOCK: usize = u16::MAX as usize;
+
+    // Deflate with a 32K window, no preset dictionary, and a check that
+    // makes the header a multiple of 31
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(BLOCK).peekable();
+    if blocks.peek().is_none() {
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let length = block.len() as u16;
+        stream.push(last as u8);
+        stream.extend(length.to_le_bytes());
+        stream.extend((!length).to_le_bytes());
+        stream.extend(block);
+    }
+    stream.extend(adler32(data).to_be_bytes());
+
+    stream
+}
+
+/// Each row as runs of one colour, `(x, length, colour)`.
+fn runs(row: &[Colour]) -> Vec<(usize, usize, Colour)> {
+    let mut runs: Vec<(usize, usize, Colour)> = vec![];
+    for (x, &colour) in row.iter().enumerate() {
+        match runs.last_mut() {
+            Some((_, length, last)) if *last == colour => *length += 1,
+            _ => runs.push((x, 1, colour)),
+        }
+    }
+    runs
+}
+
+/// Writes `frames` as one SVG. More than one frame makes an animation that
+/// shows each in turn, `fps` a second, and loops forever.
+pub fn write_svg(frames: &[&Image], fps: f64, out: &mut dyn Write) -> std::io::Result<()> {
+    let width = frames.iter().map(|frame| frame.width).max().unwrap_or(0);
+    let height = frames.iter().map(|frame| frame.height).max().unwrap_or(0);
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" shape-rendering="crispEdges">"#
+    )?;
+
+    let count = frames.len();
+    let duration = count as f64 / fps;
+    for (i, frame) in frames.iter().enumerate() {
+        if count == 1 {
+            writeln!(out, "<g>")?;
+        } else {
+            let (start, end) = (i as f64 / count as f64, (i + 1) as f64 / count as f64);
+            let (values, key_times) = match i {
+                0 => ("visible;hidden".to_owned(), format!("0;{end}")),
+                _ => (
+                    "hidden;visible;hidden".to_owned(),
+                    format!("0;{start};{end}"),
+                ),
+            };
+            writeln!(out, r#"<g visibility="hidden">"#)?;
+            writeln!(
+                out,
+                r#"<animate attributeName="visibility" values="{values}" keyTimes="{key_times}" dur="{duration}s" calcMode="discrete" repeatCount="indefinite"/>"#
+            )?;
+        }
+
+        for (y, row) in frame.rows().enumerate() {
+            for (x, length, colour) in runs(row) {
+                writeln!(
+                    out,
+                    r#"<rect x="{x}" y="{y}" width="{length}" height="1" fill="{colour}"/>"#
+                )?;
+            }
+        }
+        writeln!(out, "</g>")?;
+    }
+
+    writeln!(out, "</svg>")
+}
+
+/// Saves `frames` to `path`. An SVG holds all of them as an animation, at
+/// `fps` frames a second. Other formats can only hold one, so each frame
+/// is numbered and saved next to `path`: `trail.png` becomes
+/// `trail-0001.png`, `trail-0002.png` and so on.
+pub fn save_frames(frames: &[Image], fps: f64, path: &Path) -> Result<Vec<PathBuf>> {
+    let format = Format::from_path(path)?;
+    match (format, frames) {
+        (_, []) => Err(e!("There are no frames to save")),
+        (Format::Svg, frames) => {
+            let frames = frames.iter().collect::<Vec<_>>();
+            write_file(path, |out| write_svg(&frames, fps, out))?;
+            Ok(vec![path.to_owned()])
+        }
+        (_, [frame]) => {
+            frame.save(path)?;
+            Ok(vec![path.to_owned()])
+        }
+        (format, frames) => {
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            let extension = path.extension().unwrap_or_default().to_string_lossy();
+            let digits = frames.len().to_string().len().max(4);
+
+            frames
+                .iter()
+                .enumerate()
+                .map(|(i, frame)| {
+                    let path =
+                        path.with_file_name(format!("{stem}-{:0digits$}.{extension}", i + 1));
+                    write_file(&path, |out| frame.write(format, out))?;
+                    Ok(path)
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{adler32, crc32, runs, write_svg, zlib_stored, Colour, Gradient, Image, Palette};
+    use crate::Grid;
+
+    const RED: Colour = Colour::rgb(255, 0, 0);
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(&[b"IEND"]), 0xae42_6082);
+        assert_eq!(crc32(&[b"IE", b"ND"]), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn stored_blocks() {
+        let data = vec![7; 70_000];
+        let stream = zlib_stored(&data);
+
+        assert_eq!(&stream[..2], [0x78, 0x01]);
+        // A full block, then a final one with what's left
+        assert_eq!(&stream[2..7], [0, 0xff, 0xff, 0, 0]);
+        assert_eq!(&stream[65_542..65_547], [1, 0x71, 0x11, 0x8e, 0xee]);
+        assert_eq!(stream.len(), 2 + 5 + 65_535 + 5 + 4465 + 4);
+
+        assert_eq!(
+            zlib_stored(&[]),
+            [0x78, 0x01, 1, 0, 0, 0xff, 0xff, 0, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn formats() -> std::io::Result<()> {
+        let grid = Grid::from_rows(vec![vec![true, false], vec![false, false]]).unwrap();
+        let image = Image::from_grid(&grid, &|on: &bool| if *on { RED } else { Colour::BLACK });
+
+        let mut ppm = vec![];
+        image.write_ppm(&mut ppm)?;
+        assert_eq!(ppm, b"P6\n2 2\n255\n\xff\0\0\0\0\0\0\0\0\0\0\0");
+
+        let mut png = vec![];
+        image.write_png(&mut png)?;
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[8..16], b"\0\0\0\x0dIHDR");
+        assert_eq!(&png[16..29], b"\0\0\0\x02\0\0\0\x02\x08\x02\0\0\0");
+        assert_eq!(&png[png.len() - 12..], b"\0\0\0\0IEND\xae\x42\x60\x82");
+
+        let mut svg = vec![];
+        write_svg(&[&image], 0., &mut svg)?;
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.contains(r##"<rect x="0" y="0" width="1" height="1" fill="#ff0000"/>"##));
+        assert!(svg.contains(r##"<rect x="0" y="1" width="2" height="1" fill="#000000"/>"##));
+        assert!(!svg.contains("<animate"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn animated_svg() -> std::io::Result<()> {
+        let frames = [Image::new(1, 1, RED), Image::new(1, 1, Colour::WHITE)];
+        let mut svg = vec![];
+        write_svg(&frames.iter().collect::<Vec<_>>(), 4., &mut svg)?;
+        let svg = String::from_utf8(svg).unwrap();
+
+        assert_eq!(svg.matches("<animate ").count(), 2);
+        assert!(svg.contains(r#"values="hidden;visible;hidden" keyTimes="0;0.5;1" dur="0.5s""#));
+
+        Ok(())
+    }
+
+    #[test]
+    fn palettes() {
+        let heat = Gradient::heat(0., 10.);
+        assert_eq!(heat.colour(&0u8), Colour::rgb(16, 16, 64));
+        assert_eq!(heat.colour(&5u8), Colour::rgb(200, 32, 32));
+        assert_eq!(heat.colour(&20u8), Colour::rgb(255, 230, 80));
+        assert_eq!(Gradient::grey(0., 2.).at(1.), Colour::rgb(128, 128, 128));
+
+        assert_eq!(
+            runs(&[RED, RED, Colour::BLACK]),
+            [(0, 2, RED), (2, 1, Colour::BLACK)]
+        );
+        assert_eq!(Image::new(1, 2, RED).scaled(2), Image::new(2, 4, RED));
+    }
+}
@@ -0,0 +1,155 @@
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    io::{IsTerminal, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::{Day, Failure, Result};
+
+/// Somewhere a puzzle input might be loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Finds the input for `day`, trying each of these in order:
+///
+/// 1. `explicit`, usually given with `--input <path>`
+/// 2. stdin, when something is piped into it
+/// 3. the `input` file in the day's crate
+/// 4. `inputs/<year>/<day>.txt` under the current directory
+pub fn get_input(day: &Day, explicit: Option<&Path>) -> Result<String> {
+    if let Some(path) = explicit {
+        return read_file(path);
+    }
+
+    let mut checked = vec![];
+
+    let stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        checked.push(format!("{}: not piped", InputSource::Stdin));
+    } else {
+        let data = read_stdin()?;
+        if !data.is_empty() {
+            return Ok(data);
+        }
+        checked.push(format!("{}: empty", InputSource::Stdin));
+    }
+
+    if let Some(path) = input_file(day) {
+        return read_file(&path);
+    }
+    checked.extend(
+        default_paths(day)
+            .iter()
+            .map(|path| format!("{}: not found", path.display())),
+    );
+
+    Err(Failure::from(format!(
+        "Couldn't find an input for {} day {}. Checked:\n  {}",
+        day.year,
+        day.day,
+        checked.join("\n  ")
+    )))
+}
+
+/// The first of `day`'s usual input files that exists, for when several days
+/// are run at once and stdin can't be shared between them.
+pub fn input_file(day: &Day) -> Option<PathBuf> {
+    default_paths(day).into_iter().find(|path| path.is_file())
+}
+
+fn default_paths(day: &Day) -> [PathBuf; 2] {
+    [
+        Path::new(day.crate_dir).join("input"),
+        Path::new("inputs")
+            .join(day.year.to_string())
+            .join(format!("{:02}.txt", day.day)),
+    ]
+}
+
+fn read_stdin() -> Result<String> {
+    let mut data = String::new();
+
+    std::io::stdin()
+        .lock()
+        .read_to_string(&mut data)
+        .map_err(|err| Failure::io("Couldn't read stdin", err))?;
+
+    Ok(data)
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path)
+        .map_err(|err| Failure::io(format!("Couldn't read {}", path.display()), err))
+}
+
+/// Irons out the differences between editors: strips a leading byte order
+/// mark, turns CRLF line endings into LF and drops trailing newlines.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+
+    let input = if input.contains('\r') {
+        Cow::Owned(input.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(input)
+    };
+
+    match input {
+        Cow::Borrowed(input) => Cow::Borrowed(input.trim_end_matches('\n')),
+        Cow::Owned(input) => Cow::Owned(input.trim_end_matches('\n').to_owned()),
+    }
+}
+
+/// Every line of a normalized input. Unlike `split('\n')`, an empty input has
+/// no lines.
+pub fn lines(input: &str) -> impl Iterator<Item = &str> {
+    input.split_terminator('\n')
+}
+
+/// Every blank-line separated block of a normalized input, skipping any
+/// empty blocks left by runs of blank lines.
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split("\n\n")
+        .map(|block| block.trim_matches('\n'))
+        .filter(|block| !block.is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn normalize() {
+        assert_eq!(super::normalize("1\n2\n"), "1\n2");
+        assert_eq!(super::normalize("1\r\n2\r\n\r\n"), "1\n2");
+        assert_eq!(super::normalize("\u{feff}A Y\nB X"), "A Y\nB X");
+        assert_eq!(super::normalize("  [D]\n"), "  [D]");
+        assert_eq!(super::normalize(""), "");
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(super::lines("a\n\nb").collect::<Vec<_>>(), ["a", "", "b"]);
+        assert_eq!(super::lines("").count(), 0);
+    }
+
+    #[test]
+    fn blocks() {
+        assert_eq!(
+            super::blocks("1\n2\n\n3\n\n\n\n4").collect::<Vec<_>>(),
+            ["1\n2", "3", "4"]
+        );
+        assert_eq!(super::blocks("").count(), 0);
+    }
+}
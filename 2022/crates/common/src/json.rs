@@ -0,0 +1,207 @@
+//! Just enough JSON to write machine-readable results.
+
+use std::fmt::{Display, Write};
+
+use crate::{Answer, Failure, Location, Part};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(i128),
+    String(String),
+    Array(Vec<Json>),
+    /// Fields are written in the order given
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn object<'a>(fields: impl IntoIterator<Item = (&'a str, Json)>) -> Json {
+        Json::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_owned(), value))
+                .collect(),
+        )
+    }
+}
+
+fn write_string(f: &mut std::fmt::Formatter<'_>, string: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+    for c in string.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c < ' ' => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+impl Display for Json {
+    /// Compact, so that each value fits on one line.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(bool) => write!(f, "{bool}"),
+            Json::Number(number) => write!(f, "{number}"),
+            Json::String(string) => write_string(f, string),
+            Json::Array(items) => {
+                f.write_char('[')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(fields) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+impl From<bool> for Json {
+    fn from(bool: bool) -> Self {
+        Json::Bool(bool)
+    }
+}
+
+macro_rules! json_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Json {
+                fn from(number: $t) -> Self {
+                    Json::Number(number as i128)
+                }
+            }
+        )*
+    };
+}
+
+json_from_number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl From<&str> for Json {
+    fn from(string: &str) -> Self {
+        Json::String(string.to_owned())
+    }
+}
+
+impl From<String> for Json {
+    fn from(string: String) -> Self {
+        Json::String(string)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+impl<T: Into<Json>> From<Vec<T>> for Json {
+    fn from(items: Vec<T>) -> Self {
+        Json::Array(items.into_iter().map(Into::into).collect())
+    }
+}
+
+/// `1` or `2`
+impl From<Part> for Json {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => Json::Number(1),
+            Part::Two => Json::Number(2),
+        }
+    }
+}
+
+/// Numbers stay numbers, and text, including multi-line renders, becomes a
+/// string.
+impl From<&Answer> for Json {
+    fn from(answer: &Answer) -> Self {
+        match answer {
+            Answer::Number(number) => Json::Number(*number),
+            Answer::Text(text) => Json::String(text.clone()),
+        }
+    }
+}
+
+impl From<&Location> for Json {
+    fn from(location: &Location) -> Self {
+        Json::object([
+            ("line", location.line.into()),
+            ("column", location.column().into()),
+            ("text", location.text().into()),
+        ])
+    }
+}
+
+/// The kind, message and location come from the root cause, under any
+/// context. The report has everything, for showing to people.
+impl From<&Failure> for Json {
+    fn from(failure: &Failure) -> Self {
+        let mut cause = failure;
+        while let Failure::Context { source, .. } = cause {
+            cause = source;
+        }
+
+        Json::object([
+            ("kind", cause.kind().into()),
+            ("message", cause.message().into()),
+            ("location", cause.location().map(Json::from).into()),
+            ("report", failure.report().into()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Json;
+    use crate::{Answer, Failure, Location};
+
+    #[test]
+    fn write() {
+        let json = Json::object([
+            ("answer", (&Answer::from("#.\n.#")).into()),
+            ("quote", "say \"hi\"\t\\".into()),
+            ("bell", "\u{7}".into()),
+            ("times", vec![1u64, 20].into()),
+            ("missing", None::<u8>.into()),
+            ("ok", true.into()),
+        ]);
+
+        assert_eq!(
+            json.to_string(),
+            r##"{"answer":"#.\n.#","quote":"say \"hi\"\t\\","bell":"\u0007","times":[1,20],"missing":null,"ok":true}"##
+        );
+    }
+
+    #[test]
+    fn failure() {
+        let failure = Failure::parse("Expected a number")
+            .at(Location::in_input("1\nx", 2..3))
+            .context("In example.txt");
+
+        assert_eq!(
+            Json::from(&failure).to_string(),
+            format!(
+                r#"{{"kind":"parse","message":"Expected a number","location":{{"line":2,"column":1,"text":"x"}},"report":{}}}"#,
+                Json::from(failure.report())
+            )
+        );
+    }
+}
@@ -1,20 +1,34 @@
-use std::{fmt::Display, io::Read};
+pub mod animate;
+pub mod checkpoint;
+mod error;
+pub mod examples;
+pub mod fuzz;
+pub mod generate;
+pub mod geom;
+mod grid;
+pub mod image;
+mod input;
+pub mod json;
+pub mod log;
+mod scanner;
+pub mod search;
+pub mod snapshot;
+pub mod stream;
+mod unwind;
 
-#[derive(Debug)]
-pub struct Failure(String);
+use std::{any::Any, fmt::Display, io::Write, path::PathBuf, process::ExitCode};
 
-impl Display for Failure {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)?;
-        Ok(())
-    }
-}
+use checkpoint::{Checkpoint, Progress, Saved};
+use image::Image;
 
-impl Failure {
-    pub fn from(value: String) -> Self {
-        Self(value)
-    }
-}
+pub use animate::Animation;
+pub use error::{Failure, Location, Step};
+pub use examples::check_examples;
+pub use generate::{Output, Rng};
+pub use grid::{Grid, Position};
+pub use input::{blocks, get_input, input_file, lines, normalize, InputSource};
+pub use scanner::Scanner;
+pub use unwind::catch_panic;
 
 #[macro_export]
 macro_rules! e {
@@ -25,15 +39,308 @@ macro_rules! e {
 
 pub type Result<T, E = Failure> = std::result::Result<T, E>;
 
-pub fn get_input() -> Result<String> {
-    let stdin = std::io::stdin();
-    let mut stdin = stdin.lock();
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
 
-    let mut data = String::new();
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{number}"),
+            Answer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
 
-    stdin
-        .read_to_string(&mut data)
-        .map_err(|err| e!("Couldn't read stdin: {err}"))?;
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(number: $t) -> Self {
+                    Answer::Number(number as i128)
+                }
+            }
+        )*
+    };
+}
 
-    Ok(data)
+answer_from_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_owned())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "Part one"),
+            Part::Two => write!(f, "Part two"),
+        }
+    }
+}
+
+/// A puzzle whose input is parsed once and then shared by both parts.
+pub trait Solution {
+    type Parsed: Send + 'static;
+
+    fn parse(input: &str) -> Result<Self::Parsed>;
+
+    fn part_one(parsed: &Self::Parsed) -> Result<Answer>;
+
+    fn part_two(parsed: &Self::Parsed) -> Result<Answer>;
+
+    /// Writes a valid input of about `out.size` bytes, chosen by `rng`.
+    fn generate(_rng: &mut Rng, _out: &mut Output) -> std::io::Result<()> {
+        Err(std::io::ErrorKind::Unsupported.into())
+    }
+
+    /// Solves `part` with each step of the simulation shown on `animation`.
+    /// Days that aren't simulations just solve it, without showing anything.
+    fn animate(parsed: &Self::Parsed, part: Part, _animation: &mut Animation) -> Result<Answer> {
+        match part {
+            Part::One => Self::part_one(parsed),
+            Part::Two => Self::part_two(parsed),
+        }
+    }
+
+    /// Pictures of the state `part` ends up in, or of how it got there when
+    /// there's more than one.
+    fn render(_parsed: &Self::Parsed, _part: Part) -> Result<Vec<Image>> {
+        Err(e!("There aren't any pictures of this day"))
+    }
+
+    /// Runs `part`'s simulation until `until` steps have been taken, or to
+    /// the end when that's `None`. It starts from a checkpoint's step and
+    /// state when `from` has one, and from the beginning otherwise.
+    fn simulate(
+        _parsed: &Self::Parsed,
+        _part: Part,
+        _from: Option<Saved>,
+        _until: Option<usize>,
+    ) -> Result<Progress> {
+        Err(e!("This day isn't a simulation that can be checkpointed"))
+    }
+}
+
+/// The output of [`Day::parse`], ready to be handed to either part.
+pub struct Parsed(Box<dyn Any + Send>);
+
+/// A registered puzzle solver. Each day crate exposes one of these as `DAY`
+/// and the `aoc` runner dispatches to it by `year` and `day`.
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub name: &'static str,
+    /// The day crate's manifest directory, where its `input` file lives
+    pub crate_dir: &'static str,
+    parse: fn(&str) -> Result<Parsed>,
+    part_one: fn(&Parsed) -> Result<Answer>,
+    part_two: fn(&Parsed) -> Result<Answer>,
+    generate: fn(&mut Rng, &mut Output) -> std::io::Result<()>,
+    animate: fn(&Parsed, Part, &mut Animation) -> Result<Answer>,
+    render: fn(&Parsed, Part) -> Result<Vec<Image>>,
+    simulate: fn(&Parsed, Part, Option<Saved>, Option<usize>) -> Result<Progress>,
+}
+
+fn parse_erased<S: Solution>(input: &str) -> Result<Parsed> {
+    Ok(Parsed(Box::new(S::parse(input)?)))
+}
+
+fn downcast<S: Solution>(parsed: &Parsed) -> Result<&S::Parsed> {
+    parsed
+        .0
+        .downcast_ref()
+        .ok_or_else(|| e!("Parsed input was handed to the wrong solver"))
+}
+
+fn part_one_erased<S: Solution>(parsed: &Parsed) -> Result<Answer> {
+    S::part_one(downcast::<S>(parsed)?)
+}
+
+fn part_two_erased<S: Solution>(parsed: &Parsed) -> Result<Answer> {
+    S::part_two(downcast::<S>(parsed)?)
+}
+
+fn animate_erased<S: Solution>(
+    parsed: &Parsed,
+    part: Part,
+    animation: &mut Animation,
+) -> Result<Answer> {
+    S::animate(downcast::<S>(parsed)?, part, animation)
+}
+
+fn render_erased<S: Solution>(parsed: &Parsed, part: Part) -> Result<Vec<Image>> {
+    S::render(downcast::<S>(parsed)?, part)
+}
+
+fn simulate_erased<S: Solution>(
+    parsed: &Parsed,
+    part: Part,
+    from: Option<Saved>,
+    until: Option<usize>,
+) -> Result<Progress> {
+    S::simulate(downcast::<S>(parsed)?, part, from, until)
+}
+
+impl Day {
+    pub const fn new<S: Solution>(
+        year: u16,
+        day: u8,
+        name: &'static str,
+        crate_dir: &'static str,
+    ) -> Self {
+        Self {
+            year,
+            day,
+            name,
+            crate_dir,
+            parse: parse_erased::<S>,
+            part_one: part_one_erased::<S>,
+            part_two: part_two_erased::<S>,
+            generate: S::generate,
+            animate: animate_erased::<S>,
+            render: render_erased::<S>,
+            simulate: simulate_erased::<S>,
+        }
+    }
+
+    /// Normalizes `input` and hands it to the solver's parser.
+    pub fn parse(&self, input: &str) -> Result<Parsed> {
+        (self.parse)(&normalize(input))
+    }
+
+    pub fn part(&self, part: Part, parsed: &Parsed) -> Result<Answer> {
+        match part {
+            Part::One => (self.part_one)(parsed),
+            Part::Two => (self.part_two)(parsed),
+        }
+    }
+
+    /// Writes a made-up input of about `size` bytes to `writer`. The same
+    /// `seed` always gives the same input.
+    pub fn generate(&self, seed: u64, size: usize, writer: &mut dyn Write) -> Result<()> {
+        let mut out = Output::new(writer, size);
+        (self.generate)(&mut Rng::new(seed), &mut out)
+            .and_then(|()| out.flush())
+            .map_err(|err| match err.kind() {
+                std::io::ErrorKind::Unsupported => {
+                    e!("{} day {} doesn't have a generator", self.year, self.day)
+                }
+                _ => Failure::io("Couldn't write the generated input", err),
+            })
+    }
+
+    /// Solves `part`, showing each step on `animation` as it goes.
+    pub fn animate(
+        &self,
+        parsed: &Parsed,
+        part: Part,
+        animation: &mut Animation,
+    ) -> Result<Answer> {
+        (self.animate)(parsed, part, animation)
+    }
+
+    /// Pictures of `part`'s state, one for each frame.
+    pub fn render(&self, parsed: &Parsed, part: Part) -> Result<Vec<Image>> {
+        (self.render)(parsed, part)
+    }
+
+    /// Runs `part` for `step` steps and saves where it got to.
+    pub fn checkpoint(&self, parsed: &Parsed, part: Part, step: usize) -> Result<Checkpoint> {
+        match (self.simulate)(parsed, part, None, Some(step))? {
+            Progress::Stopped(state) => Ok(Checkpoint {
+                year: self.year,
+                day: self.day,
+                part,
+                step,
+                state,
+            }),
+            Progress::Finished(answer) => Err(e!(
+                "{part} finished before step {step}, with the answer {answer}"
+            )),
+        }
+    }
+
+    /// Carries on from `checkpoint` to the end of its part.
+    pub fn resume(&self, parsed: &Parsed, checkpoint: &Checkpoint) -> Result<Answer> {
+        if (checkpoint.year, checkpoint.day) != (self.year, self.day) {
+            return Err(e!(
+                "The checkpoint is from {} day {}, not {} day {}",
+                checkpoint.year,
+                checkpoint.day,
+                self.year,
+                self.day
+            ));
+        }
+
+        let from = Some((checkpoint.step, checkpoint.state.as_str()));
+        match (self.simulate)(parsed, checkpoint.part, from, None)? {
+            Progress::Finished(answer) => Ok(answer),
+            Progress::Stopped(_) => Err(e!("The simulation stopped before the end")),
+        }
+    }
+
+    /// Parses `input` once and solves each of `parts` from it.
+    pub fn solve(&self, input: &str, parts: &[Part]) -> Result<Vec<(Part, Answer)>> {
+        let parsed = self.parse(input)?;
+
+        parts
+            .iter()
+            .map(|&part| Ok((part, self.part(part, &parsed)?)))
+            .collect()
+    }
+}
+
+/// Prints each part's answer, or just the answer when only one part was
+/// solved.
+pub fn print_answers(answers: &[(Part, Answer)]) {
+    match answers {
+        [(_, answer)] => println!("{answer}"),
+        answers => {
+            for (part, answer) in answers {
+                match answer {
+                    Answer::Text(text) if text.contains('\n') => println!("{part}:\n{text}"),
+                    answer => println!("{part}: {answer}"),
+                }
+            }
+        }
+    }
+}
+
+/// What a day crate's own binary does: solves both parts of `day` from the
+/// input at the path given as its first argument, or wherever [`get_input`]
+/// finds one.
+pub fn run_day(day: &Day) -> ExitCode {
+    let explicit = std::env::args_os().nth(1).map(PathBuf::from);
+
+    let solved = get_input(day, explicit.as_deref())
+        .and_then(|input| day.solve(&input, &[Part::One, Part::Two]));
+
+    match solved {
+        Ok(answers) => {
+            print_answers(&answers);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{}", err.report());
+            ExitCode::FAILURE
+        }
+    }
 }
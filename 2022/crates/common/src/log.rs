@@ -0,0 +1,127 @@
+//! Diagnostics that go to stderr, so that stdout only ever holds answers.
+//! Messages below the current level are never formatted, which keeps
+//! per-step tracing cheap when it's off.
+
+use std::{
+    fmt::{Arguments, Display},
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// How much to log, from least to most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 5] = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    /// The level for `-q`, nothing, `-v` and `-vv`.
+    pub fn from_verbosity(quiet: bool, verbose: u8) -> Level {
+        match (quiet, verbose) {
+            (true, _) => Level::Error,
+            (false, 0) => Level::Info,
+            (false, 1) => Level::Debug,
+            (false, _) => Level::Trace,
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Level::Error => write!(f, "error"),
+            Level::Warn => write!(f, "warn"),
+            Level::Info => write!(f, "info"),
+            Level::Debug => write!(f, "debug"),
+            Level::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    Level::ALL[LEVEL.load(Ordering::Relaxed) as usize]
+}
+
+/// Whether messages at `level` are being written, for skipping work that
+/// only feeds a log message.
+pub fn enabled(level: Level) -> bool {
+    level <= self::level()
+}
+
+#[doc(hidden)]
+pub fn write(level: Level, message: Arguments) {
+    // Multi-line messages like grids keep their shape under the prefix
+    let message = message.to_string();
+    if message.contains('\n') {
+        eprintln!("[{level}]\n{message}");
+    } else {
+        eprintln!("[{level}] {message}");
+    }
+}
+
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($t:tt)*) => {
+        if $crate::log::enabled($level) {
+            $crate::log::write($level, format_args!($($t)*));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($t:tt)*) => { $crate::log!($crate::log::Level::Error, $($t)*) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($t:tt)*) => { $crate::log!($crate::log::Level::Warn, $($t)*) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($t:tt)*) => { $crate::log!($crate::log::Level::Info, $($t)*) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($t:tt)*) => { $crate::log!($crate::log::Level::Debug, $($t)*) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($t:tt)*) => { $crate::log!($crate::log::Level::Trace, $($t)*) };
+}
+
+#[cfg(test)]
+mod test {
+    use super::Level;
+
+    #[test]
+    fn verbosity() {
+        assert_eq!(Level::from_verbosity(true, 2), Level::Error);
+        assert_eq!(Level::from_verbosity(false, 0), Level::Info);
+        assert_eq!(Level::from_verbosity(false, 1), Level::Debug);
+        assert_eq!(Level::from_verbosity(false, 5), Level::Trace);
+
+        assert!(Level::Warn < Level::Info);
+        assert_eq!(Level::ALL.map(|level| level as usize), [0, 1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,380 @@
+use std::{fmt::Display, ops::Range, str::FromStr};
+
+use crate::{Failure, Location, Result};
+
+/// A cursor over part of a puzzle input. Every failure it produces points
+/// at the exact position in the whole input where scanning went wrong.
+#[derive(Debug, Clone, Copy)]
+pub struct Scanner<'a> {
+    /// The whole input, so that failures can be located in it
+    source: &'a str,
+    /// Absolute position of the cursor
+    pos: usize,
+    /// Absolute position this scanner stops at
+    end: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            pos: 0,
+            end: source.len(),
+        }
+    }
+
+    /// A scanner over `slice`, which must have been borrowed from this
+    /// scanner's input.
+    fn over(&self, slice: &'a str) -> Self {
+        let start = self.offset_of(slice);
+        Self {
+            source: self.source,
+            pos: start,
+            end: start + slice.len(),
+        }
+    }
+
+    fn offset_of(&self, slice: &'a str) -> usize {
+        let offset = (slice.as_ptr() as usize).wrapping_sub(self.source.as_ptr() as usize);
+        debug_assert!(offset + slice.len() <= self.source.len());
+        offset
+    }
+
+    /// Everything left to scan.
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.pos..self.end]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.end
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// A parse failure at the cursor.
+    pub fn error(&self, message: impl Into<String>) -> Failure {
+        let len = self.peek().map_or(0, char::len_utf8);
+        self.error_at_span(self.pos..self.pos + len, message)
+    }
+
+    /// A parse failure pointing at `slice`, which must have been taken from
+    /// this scanner.
+    pub fn error_at(&self, slice: &'a str, message: impl Into<String>) -> Failure {
+        let start = self.offset_of(slice);
+        self.error_at_span(start..start + slice.len(), message)
+    }
+
+    /// A parse failure just past the end of this scanner, for when something
+    /// is missing.
+    pub fn error_at_end(&self, message: impl Into<String>) -> Failure {
+        self.error_at_span(self.end..self.end, message)
+    }
+
+    fn error_at_span(&self, span: Range<usize>, message: impl Into<String>) -> Failure {
+        Failure::parse(message).at(Location::in_input(self.source, span))
+    }
+
+    /// Gives a failure from converting `slice` a location, unless it already
+    /// has one.
+    fn locate(&self, slice: &'a str, failure: Failure) -> Failure {
+        match failure {
+            Failure::Other(message) => self.error_at(slice, message),
+            failure if failure.location().is_none() => {
+                let start = self.offset_of(slice);
+                failure.at(Location::in_input(self.source, start..start + slice.len()))
+            }
+            failure => failure,
+        }
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let taken = &self.source[self.pos..self.pos + len];
+        self.pos += len;
+        taken
+    }
+
+    /// Consumes characters for as long as `predicate` holds.
+    pub fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let len = self
+            .rest()
+            .find(|c| !predicate(c))
+            .unwrap_or(self.rest().len());
+        self.advance(len)
+    }
+
+    /// Skips spaces and tabs, but not newlines.
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(|c| c == ' ' || c == '\t');
+    }
+
+    pub fn char(&mut self) -> Result<char> {
+        let c = self
+            .peek()
+            .ok_or_else(|| self.error("Expected a character"))?;
+        self.advance(c.len_utf8());
+        Ok(c)
+    }
+
+    /// Consumes `literal`, or fails without moving.
+    pub fn literal(&mut self, literal: &str) -> Result<()> {
+        if self.try_literal(literal) {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected {literal:?}")))
+        }
+    }
+
+    /// Consumes `literal` if it's next.
+    pub fn try_literal(&mut self, literal: &str) -> bool {
+        let found = self.rest().starts_with(literal);
+        if found {
+            self.advance(literal.len());
+        }
+        found
+    }
+
+    /// Consumes a run of anything but whitespace.
+    pub fn word(&mut self) -> Result<&'a str> {
+        let word = self.take_while(|c| !c.is_whitespace());
+        if word.is_empty() {
+            return Err(self.error("Expected a word"));
+        }
+        Ok(word)
+    }
+
+    /// Consumes a word and converts it. Failures from `convert` are located
+    /// at the word.
+    pub fn word_as<T>(&mut self, convert: impl FnOnce(&'a str) -> Result<T>) -> Result<T> {
+        let word = self.word()?;
+        convert(word).map_err(|failure| self.locate(word, failure))
+    }
+
+    /// Runs `scan`, locating any failure that doesn't already have a location
+    /// at everything `scan` consumed.
+    pub fn spanned<T>(&mut self, scan: impl FnOnce(&mut Scanner<'a>) -> Result<T>) -> Result<T> {
+        let start = self.pos;
+        scan(self).map_err(|failure| self.locate(&self.source[start..self.pos], failure))
+    }
+
+    /// Consumes an optionally signed integer of any width.
+    pub fn int<T>(&mut self) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let start = self.pos;
+        let sign = if self.try_literal("-") || self.try_literal("+") {
+            1
+        } else {
+            0
+        };
+        let digits = self.take_while(|c| c.is_ascii_digit());
+
+        if digits.is_empty() {
+            self.pos = start;
+            return Err(self.error("Expected a number"));
+        }
+
+        let number = &self.source[start..start + sign + digits.len()];
+        number.parse().map_err(|err| {
+            self.error_at(
+                number,
+                format!(
+                    "Couldn't parse {number} as {}: {err}",
+                    std::any::type_name::<T>()
+                ),
+            )
+        })
+    }
+
+    /// Consumes everything up to `delimiter`, and the delimiter itself,
+    /// returning a scanner over what came before it.
+    pub fn until(&mut self, delimiter: &str) -> Result<Scanner<'a>> {
+        let len = self
+            .rest()
+            .find(delimiter)
+            .ok_or_else(|| self.error(format!("Expected {delimiter:?}")))?;
+        let taken = self.advance(len);
+        self.advance(delimiter.len());
+        Ok(self.over(taken))
+    }
+
+    /// Scans one or more items with `item`, each separated by `separator`.
+    pub fn separated<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Scanner<'a>) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let mut items = vec![item(self)?];
+        while self.try_literal(separator) {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Splits off a `key<separator>value` pair, returning the key and a
+    /// scanner over the rest as the value. Leading whitespace is skipped.
+    pub fn key_value(&mut self, separator: &str) -> Result<(&'a str, Scanner<'a>)> {
+        self.skip_whitespace();
+        let key = self.until(separator)?.rest();
+        self.skip_whitespace();
+        let value = self.over(self.rest());
+        self.pos = self.end;
+        Ok((key, value))
+    }
+
+    /// Like [`Scanner::key_value`] with a `": "` separator, but also checks
+    /// that the key is `key`.
+    pub fn field(&mut self, key: &str) -> Result<Scanner<'a>> {
+        self.skip_whitespace();
+        let start = *self;
+        let (found, value) = self.key_value(":")?;
+        if found != key {
+            return Err(start.error_at(found, format!("Expected {key:?}, found {found:?}")));
+        }
+        Ok(value)
+    }
+
+    /// Fails unless everything has been scanned.
+    pub fn finish(&self) -> Result<()> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error_at(self.rest(), format!("Unexpected {:?}", self.rest())))
+        }
+    }
+
+    /// Runs `scan` over this whole scanner, failing if anything is left.
+    pub fn parse_all<T>(mut self, scan: impl FnOnce(&mut Scanner<'a>) -> Result<T>) -> Result<T> {
+        let parsed = scan(&mut self)?;
+        self.finish()?;
+        Ok(parsed)
+    }
+
+    /// A scanner over each remaining line.
+    pub fn lines(&self) -> impl Iterator<Item = Scanner<'a>> + '_ {
+        crate::lines(self.rest()).map(|line| self.over(line))
+    }
+
+    /// A scanner over each remaining blank-line separated block.
+    pub fn blocks(&self) -> impl Iterator<Item = Scanner<'a>> + '_ {
+        crate::blocks(self.rest()).map(|block| self.over(block))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Scanner;
+    use crate::{Failure, Result};
+
+    #[test]
+    fn move_instruction() -> Result<()> {
+        let mut s = Scanner::new("move 12 from -3 to 4");
+
+        s.literal("move ")?;
+        let quantity = s.int::<u8>()?;
+        s.literal(" from ")?;
+        let origin = s.int::<i64>()?;
+        s.literal(" to ")?;
+        let destination = s.int::<usize>()?;
+        s.finish()?;
+
+        assert_eq!((quantity, origin, destination), (12, -3, 4));
+
+        Ok(())
+    }
+
+    #[test]
+    fn int_failures_are_located() {
+        let input = "1\n2\n300";
+        let s = Scanner::new(input);
+
+        let failure = s
+            .lines()
+            .map(|line| line.parse_all(Scanner::int::<u8>))
+            .collect::<Result<Vec<_>>>()
+            .expect_err("300 doesn't fit in a u8");
+
+        let location = failure.location().expect("int failures have a location");
+        assert_eq!(location.line, 3);
+        assert_eq!(location.text(), "300");
+
+        let failure = Scanner::new("x1").int::<u8>().expect_err("x isn't a digit");
+        assert_eq!(failure.location().map(|l| l.column()), Some(1));
+    }
+
+    #[test]
+    fn separated() -> Result<()> {
+        let mut s = Scanner::new("498,4 -> 498,6 -> 496,6");
+
+        let points = s.separated(" -> ", |s| {
+            let x = s.int::<u32>()?;
+            s.literal(",")?;
+            let y = s.int::<u32>()?;
+            Ok((x, y))
+        })?;
+        s.finish()?;
+
+        assert_eq!(points, [(498, 4), (498, 6), (496, 6)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn key_value_and_field() -> Result<()> {
+        let input = "Monkey 0:\n  Starting items: 79, 98\n  Test: divisible by 23";
+        let s = Scanner::new(input);
+        let mut lines = s.lines().skip(1);
+
+        let mut line = lines.next().expect("input has a second line");
+        let (key, mut value) = line.key_value(":")?;
+        assert_eq!(key, "Starting items");
+        assert_eq!(value.separated(", ", Scanner::int::<u64>)?, [79, 98]);
+
+        let mut line = lines.next().expect("input has a third line");
+        let failure = line.field("Operation").expect_err("the key is Test");
+        let location = failure.location().expect("field failures have a location");
+        assert_eq!((location.line, location.text()), (3, "Test"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn blocks_keep_their_position() {
+        let input = "1\n2\n\n3\n4";
+        let s = Scanner::new(input);
+
+        let failure = s
+            .blocks()
+            .nth(1)
+            .expect("input has two blocks")
+            .lines()
+            .nth(1)
+            .expect("block has two lines")
+            .error("here");
+
+        let location = failure.location().expect("errors have a location");
+        assert_eq!((location.line, location.column()), (5, 1));
+    }
+
+    #[test]
+    fn word_as_locates_conversion_failures() {
+        let mut s = Scanner::new("U 4 X");
+        let failure = (|| {
+            s.word()?;
+            s.skip_whitespace();
+            s.int::<u8>()?;
+            s.skip_whitespace();
+            s.word_as(|word| match word {
+                "U" => Ok(()),
+                _ => Err(Failure::from(format!("Invalid heading {word}"))),
+            })
+        })()
+        .expect_err("X isn't a heading");
+
+        assert!(matches!(failure, Failure::Parse { .. }));
+        assert_eq!(failure.location().map(|l| l.column()), Some(5));
+    }
+}
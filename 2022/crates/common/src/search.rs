@@ -0,0 +1,408 @@
+//! Shortest path searches over any graph, given a function that lists each
+//! node's neighbours. Searches are iterators over the nodes they expand, so
+//! they can be stepped through and drawn part way.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// How a node was reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Visit<N> {
+    /// `None` for the nodes the search started from
+    pub parent: Option<N>,
+    /// Total cost from the nearest start, which is the number of steps for BFS
+    pub cost: u64,
+}
+
+/// Every node a search has reached, and how.
+#[derive(Debug, Clone)]
+pub struct Visits<N>(HashMap<N, Visit<N>>);
+
+impl<N: Clone + Eq + Hash> Visits<N> {
+    fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    pub fn get(&self, node: &N) -> Option<&Visit<N>> {
+        self.0.get(node)
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.0.contains_key(node)
+    }
+
+    pub fn cost(&self, node: &N) -> Option<u64> {
+        self.get(node).map(|visit| visit.cost)
+    }
+
+    /// Every node from a start up to and including `node`.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let mut path = vec![node.clone()];
+        let mut visit = self.get(node)?;
+
+        while let Some(parent) = &visit.parent {
+            path.push(parent.clone());
+            visit = self.get(parent)?;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&N, &Visit<N>)> {
+        self.0.iter()
+    }
+}
+
+/// Breadth-first search, for when every step costs the same.
+pub struct Bfs<N, F> {
+    queue: VecDeque<N>,
+    expanded: HashSet<N>,
+    visits: Visits<N>,
+    neighbours: F,
+}
+
+impl<N, F, I> Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    /// Searches outwards from every one of `starts` at once.
+    pub fn new(starts: impl IntoIterator<Item = N>, neighbours: F) -> Self {
+        let mut visits = Visits::new();
+        let mut queue = VecDeque::new();
+
+        for start in starts {
+            if !visits.contains(&start) {
+                visits.0.insert(
+                    start.clone(),
+                    Visit {
+                        parent: None,
+                        cost: 0,
+                    },
+                );
+                queue.push_back(start);
+            }
+        }
+
+        Self {
+            queue,
+            expanded: HashSet::new(),
+            visits,
+            neighbours,
+        }
+    }
+
+    /// Expands nodes until one satisfies `is_goal`.
+    pub fn find(&mut self, is_goal: impl FnMut(&N) -> bool) -> Option<N> {
+        self.find_map(is_goal_filter(is_goal))
+    }
+
+    pub fn visits(&self) -> &Visits<N> {
+        &self.visits
+    }
+
+    /// Ends the search, keeping everything it reached.
+    pub fn into_visits(self) -> Visits<N> {
+        self.visits
+    }
+
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.visits.path_to(node)
+    }
+
+    /// Nodes that have been expanded.
+    pub fn visited(&self) -> impl Iterator<Item = &N> {
+        self.expanded.iter()
+    }
+
+    /// Nodes that have been reached but not yet expanded, next first.
+    pub fn frontier(&self) -> impl Iterator<Item = &N> {
+        self.queue.iter()
+    }
+}
+
+impl<N, F, I> Iterator for Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Item = N;
+
+    /// Expands the next node, returning it.
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        let cost = self.visits.cost(&node).expect("queued nodes are visited") + 1;
+
+        for neighbour in (self.neighbours)(&node) {
+            if !self.visits.contains(&neighbour) {
+                self.visits.0.insert(
+                    neighbour.clone(),
+                    Visit {
+                        parent: Some(node.clone()),
+                        cost,
+                    },
+                );
+                self.queue.push_back(neighbour);
+            }
+        }
+
+        self.expanded.insert(node.clone());
+        Some(node)
+    }
+}
+
+/// A* search, or Dijkstra's algorithm when there's no heuristic.
+pub struct AStar<N, F, H> {
+    heap: BinaryHeap<Reverse<(u64, u64, N)>>,
+    expanded: HashSet<N>,
+    visits: Visits<N>,
+    neighbours: F,
+    heuristic: H,
+}
+
+impl<N, F, I> AStar<N, F, fn(&N) -> u64>
+where
+    N: Clone + Eq + Hash + Ord,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    /// Dijkstra's algorithm from every one of `starts` at once. `neighbours`
+    /// gives each neighbour along with the cost of stepping to it.
+    pub fn dijkstra(starts: impl IntoIterator<Item = N>, neighbours: F) -> Self {
+        Self::new(starts, neighbours, |_| 0)
+    }
+}
+
+impl<N, F, I, H> AStar<N, F, H>
+where
+    N: Clone + Eq + Hash + Ord,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    H: FnMut(&N) -> u64,
+{
+    /// `heuristic` must never overestimate the cost to the goal, or paths
+    /// found might not be the shortest. If it isn't also consistent, nodes
+    /// can be expanded again when a cheaper route to them turns up.
+    pub fn new(starts: impl IntoIterator<Item = N>, neighbours: F, mut heuristic: H) -> Self {
+        let mut visits = Visits::new();
+        let mut heap = BinaryHeap::new();
+
+        for start in starts {
+            if !visits.contains(&start) {
+                visits.0.insert(
+                    start.clone(),
+                    Visit {
+                        parent: None,
+                        cost: 0,
+                    },
+                );
+                heap.push(Reverse((heuristic(&start), 0, start)));
+            }
+        }
+
+        Self {
+            heap,
+            expanded: HashSet::new(),
+            visits,
+            neighbours,
+            heuristic,
+        }
+    }
+
+    /// Expands nodes until one satisfies `is_goal`.
+    pub fn find(&mut self, is_goal: impl FnMut(&N) -> bool) -> Option<N> {
+        self.find_map(is_goal_filter(is_goal))
+    }
+
+    pub fn visits(&self) -> &Visits<N> {
+        &self.visits
+    }
+
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.visits.path_to(node)
+    }
+
+    /// Nodes that have been expanded, and not since reached more cheaply.
+    /// Their costs are final unless the heuristic is inconsistent.
+    pub fn visited(&self) -> impl Iterator<Item = &N> {
+        self.expanded.iter()
+    }
+
+    /// Nodes that have been reached but not yet expanded, in no particular
+    /// order.
+    pub fn frontier(&self) -> impl Iterator<Item = &N> {
+        self.heap.iter().filter_map(|Reverse((_, cost, node))| {
+            let current = !self.expanded.contains(node) && self.visits.cost(node) == Some(*cost);
+            current.then_some(node)
+        })
+    }
+}
+
+impl<N, F, I, H> Iterator for AStar<N, F, H>
+where
+    N: Clone + Eq + Hash + Ord,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    H: FnMut(&N) -> u64,
+{
+    type Item = N;
+
+    /// Expands the cheapest node, returning it.
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, cost) = loop {
+            let Reverse((_, cost, node)) = self.heap.pop()?;
+            // Skip entries that were superseded by a cheaper route
+            if !self.expanded.contains(&node) && self.visits.cost(&node) == Some(cost) {
+                break (node, cost);
+            }
+        };
+
+        for (neighbour, step) in (self.neighbours)(&node) {
+            let cost = cost + step;
+            if self
+                .visits
+                .cost(&neighbour)
+                .is_none_or(|known| cost < known)
+            {
+                self.visits.0.insert(
+                    neighbour.clone(),
+                    Visit {
+                        parent: Some(node.clone()),
+                        cost,
+                    },
+                );
+                // Its descendants were costed from the dearer route
+                self.expanded.remove(&neighbour);
+                let priority = cost + (self.heuristic)(&neighbour);
+                self.heap.push(Reverse((priority, cost, neighbour)));
+            }
+        }
+
+        self.expanded.insert(node.clone());
+        Some(node)
+    }
+}
+
+fn is_goal_filter<N>(mut is_goal: impl FnMut(&N) -> bool) -> impl FnMut(N) -> Option<N> {
+    move |node| is_goal(&node).then_some(node)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AStar, Bfs};
+    use crate::{geom::UPoint, Grid, Result};
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#.E.
+.##...#.";
+
+    fn maze() -> Result<Grid<char>> {
+        Grid::parse(MAZE, Ok)
+    }
+
+    fn open_neighbours(maze: &Grid<char>, point: &UPoint) -> Vec<UPoint> {
+        point
+            .neighbours()
+            .filter(|p| maze.get(*p).is_some_and(|c| *c != '#'))
+            .collect()
+    }
+
+    #[test]
+    fn bfs() -> Result<()> {
+        let maze = maze()?;
+        let start = UPoint::new(0, 0);
+        let end = UPoint::new(6, 2);
+
+        let mut search = Bfs::new([start], |p| open_neighbours(&maze, p));
+        let found = search.find(|p| *p == end);
+
+        assert_eq!(found, Some(end));
+        assert_eq!(search.visits().cost(&end), Some(10));
+
+        let path = search.path_to(&end).expect("end was found");
+        assert_eq!(path.len(), 11);
+        assert_eq!(path.first(), Some(&start));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        assert!(search.visited().any(|p| *p == start));
+        assert!(search.frontier().all(|p| !search.visited().any(|v| v == p)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn multi_source_bfs() -> Result<()> {
+        let maze = maze()?;
+        let starts = [UPoint::new(0, 0), UPoint::new(7, 0)];
+
+        let mut search = Bfs::new(starts, |p| open_neighbours(&maze, p));
+        search.by_ref().for_each(drop);
+
+        let end = UPoint::new(6, 2);
+        assert_eq!(search.visits().cost(&end), Some(3));
+        assert_eq!(
+            search.path_to(&end).and_then(|path| path.first().copied()),
+            Some(starts[1])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn dijkstra_and_a_star() -> Result<()> {
+        let maze = maze()?;
+        let start = UPoint::new(0, 0);
+        let end = UPoint::new(6, 2);
+        // Stepping onto a row costs one more than the row number
+        let weighted = |p: &UPoint| {
+            open_neighbours(&maze, p)
+                .into_iter()
+                .map(|n| (n, n.y as u64 + 1))
+                .collect::<Vec<_>>()
+        };
+
+        let mut dijkstra = AStar::dijkstra([start], weighted);
+        assert_eq!(dijkstra.find(|p| *p == end), Some(end));
+        let dijkstra_cost = dijkstra.visits().cost(&end);
+        let dijkstra_expanded = dijkstra.visited().count();
+
+        let mut a_star = AStar::new([start], weighted, |p: &UPoint| p.manhattan(end) as u64);
+        assert_eq!(a_star.find(|p| *p == end), Some(end));
+
+        assert_eq!(dijkstra_cost, Some(29));
+        assert_eq!(a_star.visits().cost(&end), dijkstra_cost);
+        assert!(a_star.visited().count() <= dijkstra_expanded);
+
+        let path = a_star.path_to(&end).expect("end was found");
+        let path_cost = path.iter().skip(1).map(|p| p.y as u64 + 1).sum::<u64>();
+        assert_eq!(Some(path_cost), dijkstra_cost);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reopens_with_an_inconsistent_heuristic() {
+        // S-A-C-G costs 7, but C is expanded first through the dearer S-C
+        let neighbours = |node: &char| match node {
+            'S' => vec![('A', 1), ('C', 3)],
+            'A' => vec![('C', 1)],
+            'C' => vec![('G', 5)],
+            _ => vec![],
+        };
+        // Admissible, since A is 6 from G, but not consistent
+        let heuristic = |node: &char| if *node == 'A' { 6 } else { 0 };
+
+        let mut a_star = AStar::new(['S'], neighbours, heuristic);
+        assert_eq!(a_star.find(|node| *node == 'G'), Some('G'));
+        assert_eq!(a_star.visits().cost(&'G'), Some(7));
+        assert_eq!(a_star.path_to(&'G'), Some(vec!['S', 'A', 'C', 'G']));
+    }
+}
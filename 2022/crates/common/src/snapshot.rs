@@ -0,0 +1,236 @@
+//! Snapshot tests for renders that are easier to look at than to write out
+//! by hand. Each render is kept in `snapshots/<name>.snap` next to the
+//! crate, and a render that no longer matches fails with a line by line
+//! diff against it.
+//!
+//! Snapshots are only ever written when `AOC_UPDATE_SNAPSHOTS=1` is set, so
+//! that a change to a render is something that's looked at and accepted.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{e, Failure, Result};
+
+/// Lines of context kept around each change in a diff
+const CONTEXT: usize = 2;
+
+fn updating() -> bool {
+    std::env::var("AOC_UPDATE_SNAPSHOTS").is_ok_and(|update| update == "1")
+}
+
+/// Where the snapshot called `name`, of the crate in `crate_dir`, is kept.
+pub fn path(crate_dir: &str, name: &str) -> PathBuf {
+    Path::new(crate_dir)
+        .join("snapshots")
+        .join(format!("{name}.snap"))
+}
+
+/// Checks `actual` against the snapshot called `name`, or accepts it as the
+/// new snapshot when `AOC_UPDATE_SNAPSHOTS=1`.
+pub fn check(crate_dir: &str, name: &str, actual: &str) -> Result<()> {
+    compare(&path(crate_dir, name), actual, updating())
+}
+
+/// Checks a render against the snapshot called `name` in the calling crate's
+/// `snapshots` directory, panicking with the diff when it doesn't match.
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $render:expr) => {
+        if let Err(err) =
+            $crate::snapshot::check(env!("CARGO_MANIFEST_DIR"), $name, &$render.to_string())
+        {
+            panic!("{}", err.report());
+        }
+    };
+}
+
+/// Snapshots always end with a newline, whether or not their render does.
+fn normalise(render: &str) -> String {
+    format!("{}\n", render.trim_end_matches('\n'))
+}
+
+fn compare(path: &Path, actual: &str, update: bool) -> Result<()> {
+    let actual = normalise(actual);
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => Some(expected),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+        Err(err) => {
+            return Err(Failure::io(
+                format!("Couldn't read {}", path.display()),
+                err,
+            ))
+        }
+    };
+
+    if expected.as_deref() == Some(actual.as_str()) {
+        return Ok(());
+    }
+
+    if update {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|err| Failure::io(format!("Couldn't create {}", dir.display()), err))?;
+        }
+        return fs::write(path, &actual)
+            .map_err(|err| Failure::io(format!("Couldn't write {}", path.display()), err));
+    }
+
+    let report = match expected {
+        Some(expected) => format!(
+            "{} doesn't match:\n{}",
+            path.display(),
+            diff(&expected, &actual)
+        ),
+        None => format!(
+            "{} doesn't exist yet. The render was:\n{actual}",
+            path.display()
+        ),
+    };
+
+    Err(e!(
+        "{report}\nRerun with AOC_UPDATE_SNAPSHOTS=1 to accept the new render"
+    ))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// The fewest lines to remove from `old` and add to it to make `new`, found
+/// from their longest common subsequence.
+fn changes<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Change<'a>> {
+    // common[i][j] is how many lines old[i..] and new[j..] have in common
+    let mut common = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = match old[i] == new[j] {
+                true => common[i + 1][j + 1] + 1,
+                false => common[i + 1][j].max(common[i][j + 1]),
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut changes = vec![];
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            changes.push(Change::Same(old[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            changes.push(Change::Removed(old[i]));
+            i += 1;
+        } else {
+            changes.push(Change::Added(new[j]));
+            j += 1;
+        }
+    }
+
+    changes
+}
+
+/// How `new` differs from `old`, as unified diff hunks with `-` for the
+/// snapshot and `+` for the new render.
+pub fn diff(old: &str, new: &str) -> String {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+    let changes = changes(&old, &new);
+
+    // Where each change is, counting lines of the old and new texts from 1
+    let mut numbers = Vec::with_capacity(changes.len());
+    let (mut old_line, mut new_line) = (1, 1);
+    for change in &changes {
+        numbers.push((old_line, new_line));
+        match change {
+            Change::Same(_) => (old_line, new_line) = (old_line + 1, new_line + 1),
+            Change::Removed(_) => old_line += 1,
+            Change::Added(_) => new_line += 1,
+        }
+    }
+
+    let edits = (0..changes.len())
+        .filter(|&i| !matches!(changes[i], Change::Same(_)))
+        .collect::<Vec<_>>();
+
+    // Edits close enough together share a hunk
+    let mut hunks: Vec<(usize, usize)> = vec![];
+    for &edit in &edits {
+        match hunks.last_mut() {
+            Some((_, end)) if edit <= *end + 2 * CONTEXT + 1 => *end = edit,
+            _ => hunks.push((edit, edit)),
+        }
+    }
+
+    let mut report = String::new();
+    for (first, last) in hunks {
+        let start = first.saturating_sub(CONTEXT);
+        let end = (last + CONTEXT + 1).min(changes.len());
+        let hunk = &changes[start..end];
+        let count = |keep: fn(&Change) -> bool| hunk.iter().filter(|change| keep(change)).count();
+        let (old_start, new_start) = numbers[start];
+
+        report.push_str(&format!(
+            "@@ -{old_start},{} +{new_start},{} @@\n",
+            count(|change| !matches!(change, Change::Added(_))),
+            count(|change| !matches!(change, Change::Removed(_))),
+        ));
+        for change in hunk {
+            let line = match change {
+                Change::Same(line) => format!("  {line}\n"),
+                Change::Removed(line) => format!("- {line}\n"),
+                Change::Added(line) => format!("+ {line}\n"),
+            };
+            report.push_str(&line);
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::{compare, diff};
+
+    #[test]
+    fn diffs() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let new = "1\n2\n3\nfour\n5\n6\n7\n8\n9\n10\n11\n";
+
+        assert_eq!(
+            diff(old, new),
+            "@@ -2,5 +2,5 @@\n  2\n  3\n- 4\n+ four\n  5\n  6\n@@ -9,2 +9,3 @@\n  9\n  10\n+ 11\n"
+        );
+        assert_eq!(diff(old, old), "");
+        assert_eq!(diff("", "##\n"), "@@ -1,0 +1,1 @@\n+ ##\n");
+    }
+
+    #[test]
+    fn accepts_only_when_updating() {
+        let dir = std::env::temp_dir().join(format!("aoc-snapshot-{}", std::process::id()));
+        let path = dir.join("snapshots").join("tube.snap");
+
+        let failure = compare(&path, "#.\n.#", false).unwrap_err();
+        assert!(failure.message().contains("doesn't exist yet"));
+        assert!(!path.exists());
+
+        compare(&path, "#.\n.#", true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "#.\n.#\n");
+        compare(&path, "#.\n.#\n", false).unwrap();
+
+        let failure = compare(&path, "#.\n##", false).unwrap_err();
+        assert!(failure
+            .message()
+            .contains("@@ -1,2 +1,2 @@\n  #.\n- .#\n+ ##\n"));
+        assert!(failure
+            .message()
+            .ends_with("AOC_UPDATE_SNAPSHOTS=1 to accept the new render"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,227 @@
+//! Single-pass readers over any [`BufRead`], for inputs too big to hold in
+//! memory at once. Failures still say which line they happened on.
+
+use std::io::BufRead;
+
+use crate::{Failure, Location, Result, Scanner};
+
+/// One line of a streamed input, without its line ending.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    /// 1-based
+    pub number: usize,
+    /// Byte offset of `text` within the whole input
+    pub offset: usize,
+    pub text: String,
+}
+
+impl Line {
+    /// Runs `scan` over the whole line, failing if anything is left. Failures
+    /// are located on this line of the whole input.
+    pub fn parse_all<'a, T>(
+        &'a self,
+        scan: impl FnOnce(&mut Scanner<'a>) -> Result<T>,
+    ) -> Result<T> {
+        Scanner::new(&self.text)
+            .parse_all(scan)
+            .map_err(|failure| failure.on_line(self.number, self.offset))
+    }
+
+    /// A parse failure pointing at the whole line.
+    pub fn error(&self, message: impl Into<String>) -> Failure {
+        Failure::parse(message)
+            .at(Location::in_input(&self.text, 0..self.text.len()))
+            .on_line(self.number, self.offset)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+}
+
+/// Every line of `reader`, read as it's needed. Handles the same
+/// differences between editors as [`crate::normalize`].
+pub struct Lines<R> {
+    reader: R,
+    number: usize,
+    /// Bytes read so far
+    read: usize,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            number: 0,
+            read: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = Result<Line>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut text = String::new();
+        let number = self.number + 1;
+        let mut offset = self.read;
+
+        match self.reader.read_line(&mut text) {
+            Ok(0) => return None,
+            Ok(read) => self.read += read,
+            Err(err) => {
+                return Some(Err(Failure::io(
+                    format!("Couldn't read line {number}"),
+                    err,
+                )))
+            }
+        }
+        self.number = number;
+
+        if text.ends_with('\n') {
+            text.pop();
+            if text.ends_with('\r') {
+                text.pop();
+            }
+        }
+        if number == 1 && text.starts_with('\u{feff}') {
+            text.remove(0);
+            offset += '\u{feff}'.len_utf8();
+        }
+
+        Some(Ok(Line {
+            number,
+            offset,
+            text,
+        }))
+    }
+}
+
+/// Every byte of `reader`, keeping track of the line and column it's at.
+/// The line read so far is kept too, to show in failures.
+pub struct Bytes<R> {
+    reader: R,
+    line: usize,
+    column: usize,
+    after_newline: bool,
+    text: Vec<u8>,
+    /// Bytes read so far
+    read: usize,
+    /// Byte offset of the start of the line
+    line_start: usize,
+}
+
+impl<R: BufRead> Bytes<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: 1,
+            column: 0,
+            after_newline: false,
+            text: Vec::new(),
+            read: 0,
+            line_start: 0,
+        }
+    }
+
+    /// 1-based line and column of the byte last read.
+    pub fn position(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+
+    /// A parse failure at the byte last read, or at the character it ends
+    /// or belongs to.
+    pub fn error(&self, message: impl Into<String>) -> Failure {
+        let text = String::from_utf8_lossy(&self.text);
+        let start = text.char_indices().last().map_or(0, |(i, _)| i);
+        Failure::parse(message)
+            .at(Location::in_input(&text, start..text.len()))
+            .on_line(self.line, self.line_start)
+    }
+}
+
+impl<R: BufRead> Iterator for Bytes<R> {
+    type Item = Result<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let byte = match self.reader.fill_buf() {
+            Ok([]) => return None,
+            Ok(buffer) => buffer[0],
+            Err(err) => {
+                return Some(Err(Failure::io(
+                    format!("Couldn't read line {}", self.line),
+                    err,
+                )))
+            }
+        };
+        self.reader.consume(1);
+
+        if self.after_newline {
+            self.line += 1;
+            self.column = 0;
+            self.text.clear();
+            self.line_start = self.read;
+        }
+        self.read += 1;
+        self.column += 1;
+        self.after_newline = byte == b'\n';
+        if !self.after_newline {
+            self.text.push(byte);
+        }
+
+        Some(Ok(byte))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Bytes, Lines};
+    use crate::{Result, Scanner};
+
+    #[test]
+    fn lines() {
+        let input = "\u{feff}12\r\n\n34\nx5";
+        let lines = Lines::new(input.as_bytes())
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        let text = lines
+            .iter()
+            .map(|line| line.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(text, ["12", "", "34", "x5"]);
+        assert_eq!(lines[0].parse_all(Scanner::int::<u32>).unwrap(), 12);
+
+        let failure = lines[3].parse_all(Scanner::int::<u32>).unwrap_err();
+        let location = failure.location().unwrap();
+        assert_eq!((location.line, location.column()), (4, 1));
+        assert_eq!(&input[location.span.clone()], "x");
+        assert_eq!(lines[1].error("Empty").location().unwrap().line, 2);
+    }
+
+    #[test]
+    fn bytes() {
+        let mut bytes = Bytes::new("ab\ncd".as_bytes());
+        let read = bytes.by_ref().take(5).collect::<Result<Vec<_>>>().unwrap();
+
+        assert_eq!(read, b"ab\ncd"[..5]);
+        assert_eq!(bytes.position(), (2, 2));
+        let failure = bytes.error("Unexpected d");
+        assert_eq!(failure.message(), "Unexpected d");
+        let location = failure.location().unwrap();
+        assert_eq!((location.line, location.column()), (2, 2));
+        assert_eq!(location.source_line, "cd");
+        assert_eq!(location.span, 4..5);
+        assert!(bytes.next().is_none());
+
+        let mut bytes = Bytes::new("ab\u{e9}\n".as_bytes());
+        bytes.by_ref().take(2).for_each(drop);
+        for expected in ["ab\u{fffd}", "ab\u{e9}"] {
+            bytes.next().transpose().unwrap();
+            let location = bytes.error("Unexpected").location().cloned();
+            let location = location.unwrap_or_else(|| panic!("{expected} wasn't located"));
+            assert_eq!(location.source_line, expected);
+            assert_eq!(location.column(), 3);
+        }
+    }
+}
@@ -1,7 +0,0 @@
-use common::*;
-
-fn main() -> Result<()> {
-    let input = get_input()?;
-
-    Ok(())
-}